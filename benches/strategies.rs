@@ -0,0 +1,73 @@
+//! Compares the search strategies on random lookups.
+//!
+//! Run with `cargo bench --bench strategies`.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use smart_search::{find_jump, find_jump_multilevel, find_jump_simd, find_simd, BatchExecutor, BatchStrategy, HardwareProfile, JumpIndex, Strategy};
+
+const QUERIES: usize = 1 << 16;
+
+/// A small xorshift generator, so the benchmark needs no dependencies.
+fn random_queries(max: u64) -> Vec<u64> {
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    (0..QUERIES)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state % max
+        })
+        .collect()
+}
+
+fn bench<F>(name: &str, len: usize, queries: &[u64], mut search: F)
+where F: FnMut(u64) -> Option<usize>
+{
+    let start = Instant::now();
+    let mut hits = 0;
+    for query in queries.iter() {
+        hits += search(black_box(*query)).is_some() as usize;
+    }
+    let elapsed = start.elapsed();
+    black_box(hits);
+    println!("{:>10} {:<28} {:>8.1} ns/lookup", len, name, elapsed.as_nanos() as f64 / queries.len() as f64);
+}
+
+fn bench_batch(name: &str, len: usize, queries: &[u64], executor: BatchExecutor, collection: &[u64]) {
+    let start = Instant::now();
+    let hits = executor.find(collection, queries).iter().filter(|r| r.is_some()).count();
+    let elapsed = start.elapsed();
+    black_box(hits);
+    println!("{:>10} {:<28} {:>8.1} ns/lookup", len, name, elapsed.as_nanos() as f64 / queries.len() as f64);
+}
+
+fn main() {
+    // Detect the hardware up front so that it is not timed as part of the first lookup.
+    println!("{:?}", HardwareProfile::current());
+    for shift in [10, 16, 20, 24].iter() {
+        let len = 1 << shift;
+        let collection: Vec<u64> = (0..len as u64).map(|x| x * 2).collect();
+        let queries = random_queries(2 * len as u64);
+        let strategies = [
+            ("binary", Strategy::Binary),
+            ("branchless", Strategy::Branchless { prefetch: false }),
+            ("branchless + prefetch", Strategy::Branchless { prefetch: true }),
+            ("fibonacci", Strategy::Fibonacci),
+        ];
+
+        bench("std binary_search", len, &queries, |q| collection.binary_search(&q).ok());
+        bench("find_jump", len, &queries, |q| find_jump(&collection, &q));
+        bench("find_jump_simd", len, &queries, |q| find_jump_simd(&collection, &q));
+        bench("find_simd", len, &queries, |q| find_simd(&collection, &q));
+        bench("find_jump_multilevel", len, &queries, |q| find_jump_multilevel(&collection, &q));
+        let jump_index = JumpIndex::new(&collection);
+        bench("JumpIndex", len, &queries, |q| jump_index.find(&q));
+        for (name, strategy) in strategies.iter() {
+            bench(name, len, &queries, |q| strategy.search_by(&collection, |probe| probe.cmp(&q)).ok());
+        }
+        bench_batch("batch binary", len, &queries, BatchExecutor::new(), &collection);
+        bench_batch("batch jump", len, &queries, BatchExecutor::new().with_strategy(BatchStrategy::Jump), &collection);
+    }
+}
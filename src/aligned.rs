@@ -0,0 +1,94 @@
+use std::mem::size_of;
+use std::ops::{Index, IndexMut};
+
+/// Fixed-length storage whose first slot starts on a cache line boundary.
+///
+/// `Vec<T>` only guarantees `align_of::<T>()`, so this over-allocates by up to one line and skips
+/// the slots before the first boundary. Element sizes that do not divide the line size cannot
+/// always be aligned, in which case the storage simply starts at the beginning of the allocation.
+#[derive(Debug)]
+pub(crate) struct AlignedSlots<T> {
+    data: Vec<T>,
+    offset: usize,
+    line_size: usize,
+}
+
+impl<T> AlignedSlots<T>
+where T: Clone
+{
+    /// Allocates `len` slots, each initialised to a clone of `fill`.
+    pub(crate) fn new(len: usize, fill: &T, line_size: usize) -> Self {
+        let element_size = size_of::<T>().max(1);
+        let max_padding = if element_size < line_size { line_size / element_size } else { 0 };
+        let mut data = Vec::with_capacity(max_padding + len);
+        let base = data.as_ptr() as usize;
+        let offset = (0..max_padding).find(|k| (base + k * element_size).is_multiple_of(line_size)).unwrap_or(0);
+        data.resize(offset + len, fill.clone());
+        debug_assert_eq!(base, data.as_ptr() as usize);
+        AlignedSlots { data, offset, line_size }
+    }
+}
+
+impl<T> AlignedSlots<T> {
+    /// Creates storage with no slots.
+    pub(crate) fn empty() -> Self {
+        AlignedSlots { data: Vec::new(), offset: 0, line_size: 1 }
+    }
+
+    /// Returns a pointer to the first slot, for prefetching.
+    pub(crate) fn as_ptr(&self) -> *const T {
+        self.data.as_ptr().wrapping_add(self.offset)
+    }
+
+    /// Returns the slots as a slice.
+    pub(crate) fn as_slice(&self) -> &[T] {
+        &self.data[self.offset..]
+    }
+}
+
+impl<T> Clone for AlignedSlots<T>
+where T: Clone
+{
+    /// Clones into a fresh allocation, which is aligned anew; copying the offset would not be.
+    fn clone(&self) -> Self {
+        match self.as_slice().first() {
+            Some(first) => {
+                let mut slots = AlignedSlots::new(self.as_slice().len(), first, self.line_size);
+                slots.data[slots.offset..].clone_from_slice(self.as_slice());
+                slots
+            }
+            None => AlignedSlots::empty(),
+        }
+    }
+}
+
+impl<T> Index<usize> for AlignedSlots<T> {
+    type Output = T;
+
+    fn index(&self, idx: usize) -> &T {
+        &self.data[self.offset + idx]
+    }
+}
+
+impl<T> IndexMut<usize> for AlignedSlots<T> {
+    fn index_mut(&mut self, idx: usize) -> &mut T {
+        &mut self.data[self.offset + idx]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AlignedSlots;
+
+    #[test]
+    fn test_clone_is_aligned() {
+        let mut slots = AlignedSlots::new(100, &0u32, 64);
+        slots[7] = 7;
+        for _ in 0..8 {
+            let clone = slots.clone();
+            assert_eq!(0, clone.as_ptr() as usize % 64);
+            assert_eq!(slots.as_slice(), clone.as_slice());
+        }
+        assert!(AlignedSlots::<u32>::empty().clone().as_slice().is_empty());
+    }
+}
@@ -0,0 +1,65 @@
+use std::borrow::Borrow;
+
+use crate::jump;
+use crate::strategy::jump_size;
+use crate::BatchExecutor;
+
+/// Looks up many items in a sorted collection at once.
+///
+/// If the queries are sorted, the collection is swept once from left to right, resuming each jump
+/// search from the previous hit. Otherwise the queries are handed to a [`BatchExecutor`], which
+/// overlaps the cache misses of independent searches instead of paying for them one after another.
+pub fn find_many<T, Q>(collection: &[T], queries: &[Q]) -> Vec<Option<usize>>
+where T: Borrow<Q>, Q: Ord
+{
+    search_many(collection, queries).into_iter().map(Result::ok).collect()
+}
+
+/// Like [`find_many`], but returns insertion points on misses (see [`search`](crate::search)).
+pub fn search_many<T, Q>(collection: &[T], queries: &[Q]) -> Vec<Result<usize, usize>>
+where T: Borrow<Q>, Q: Ord
+{
+    if queries.windows(2).all(|w| w[0] <= w[1]) {
+        sweep(collection, queries)
+    } else {
+        BatchExecutor::new().search(collection, queries)
+    }
+}
+
+/// Searches for sorted queries with jump searches that each start where the previous one ended.
+fn sweep<T, Q>(collection: &[T], queries: &[Q]) -> Vec<Result<usize, usize>>
+where T: Borrow<Q>, Q: Ord
+{
+    let jump_size = jump_size(collection.len());
+    let mut start = 0;
+    queries
+        .iter()
+        .map(|item| {
+            let result = match jump::search_by(&collection[start..], jump_size, |probe| probe.borrow().cmp(item)) {
+                Ok(idx) => Ok(start + idx),
+                Err(idx) => Err(start + idx),
+            };
+            start = result.unwrap_or_else(|idx| idx);
+            result
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_many, search_many};
+
+    #[test]
+    fn test_sorted_and_unsorted_queries() {
+        let vec: Vec<u32> = (0..1000).map(|x| x * 3).collect();
+        let sorted: Vec<u32> = (0..3010).step_by(7).collect();
+        let unsorted: Vec<u32> = sorted.iter().map(|x| (x * 7919) % 3010).collect();
+        for queries in [sorted, unsorted].iter() {
+            let expected: Vec<Result<usize, usize>> = queries.iter().map(|x| vec.binary_search(x)).collect();
+            assert_eq!(expected, search_many(&vec, queries));
+        }
+        assert_eq!(vec![Some(2), None, Some(0)], find_many(&vec, &[6, 7, 0]));
+        assert_eq!(vec![None, None], find_many(&[] as &[u32], &[6, 7]));
+        assert!(find_many(&vec, &[] as &[u32]).is_empty());
+    }
+}
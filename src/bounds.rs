@@ -0,0 +1,106 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::ops::Range;
+
+use crate::{HardwareProfile, Strategy};
+
+/// Returns the index of the first element that is not less than `item`.
+///
+/// Unlike [`find`](crate::find), this is deterministic in the presence of duplicates.
+pub fn lower_bound<T, Q>(collection: &[T], item: &Q) -> usize
+where T: Borrow<Q>, Q: ?Sized + Ord
+{
+    lower_bound_by(collection, |probe| probe.borrow().cmp(item))
+}
+
+/// Like [`lower_bound`], but with a comparator that orders a probed element relative to the target.
+pub fn lower_bound_by<T, F>(collection: &[T], mut f: F) -> usize
+where F: FnMut(&T) -> Ordering
+{
+    partition_point(collection, &HardwareProfile::current_or_conservative(), |probe| f(probe) == Ordering::Less)
+}
+
+/// Returns the index of the first element that is greater than `item`.
+pub fn upper_bound<T, Q>(collection: &[T], item: &Q) -> usize
+where T: Borrow<Q>, Q: ?Sized + Ord
+{
+    upper_bound_by(collection, |probe| probe.borrow().cmp(item))
+}
+
+/// Like [`upper_bound`], but with a comparator that orders a probed element relative to the target.
+pub fn upper_bound_by<T, F>(collection: &[T], mut f: F) -> usize
+where F: FnMut(&T) -> Ordering
+{
+    partition_point(collection, &HardwareProfile::current_or_conservative(), |probe| f(probe) != Ordering::Greater)
+}
+
+/// Returns the range of indices of the elements equal to `item`, which is empty (at the insertion point) if there are none.
+pub fn equal_range<T, Q>(collection: &[T], item: &Q) -> Range<usize>
+where T: Borrow<Q>, Q: ?Sized + Ord
+{
+    equal_range_by(collection, |probe| probe.borrow().cmp(item))
+}
+
+/// Like [`equal_range`], but with a comparator that orders a probed element relative to the target.
+pub fn equal_range_by<T, F>(collection: &[T], f: F) -> Range<usize>
+where F: FnMut(&T) -> Ordering
+{
+    equal_range_with_profile(collection, &HardwareProfile::current_or_conservative(), f)
+}
+
+/// Returns the number of elements equal to `item`.
+pub fn count<T, Q>(collection: &[T], item: &Q) -> usize
+where T: Borrow<Q>, Q: ?Sized + Ord
+{
+    equal_range(collection, item).len()
+}
+
+/// Like [`count`], but with a comparator that orders a probed element relative to the target.
+pub fn count_by<T, F>(collection: &[T], f: F) -> usize
+where F: FnMut(&T) -> Ordering
+{
+    equal_range_by(collection, f).len()
+}
+
+/// Internal `equal_range`, which only searches for the upper bound past the lower bound.
+fn equal_range_with_profile<T, F>(collection: &[T], profile: &HardwareProfile, mut f: F) -> Range<usize>
+where F: FnMut(&T) -> Ordering
+{
+    let start = partition_point(collection, profile, |probe| f(probe) == Ordering::Less);
+    let end = start + partition_point(&collection[start..], profile, |probe| f(probe) == Ordering::Equal);
+    start..end
+}
+
+/// Internal cache-aware partition point, tuned against an explicit hardware profile.
+pub(crate) fn partition_point<T, P>(collection: &[T], profile: &HardwareProfile, pred: P) -> usize
+where P: FnMut(&T) -> bool
+{
+    Strategy::select::<T>(profile, collection.len()).partition_point(collection, pred)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::equal_range_with_profile;
+    use crate::{CacheLevel, HardwareProfile, Strategy};
+
+    #[test]
+    fn test_bounds_with_duplicates() {
+        let vec: Vec<u32> = (0..300).map(|x| x / 3 * 2).collect();
+        let line_sizes = [64, 128, 4096];
+        for x in 0..202 {
+            let expected = vec.partition_point(|v| *v < x)..vec.partition_point(|v| *v <= x);
+            for strategy in [Strategy::Linear, Strategy::Jump, Strategy::Binary].iter() {
+                assert_eq!(expected.start, strategy.partition_point(&vec, |v| *v < x));
+                assert_eq!(expected.end, strategy.partition_point(&vec, |v| *v <= x));
+            }
+            for line_size in line_sizes.iter() {
+                let cache = CacheLevel { size: 1 << 20, line_size: *line_size, associativity: 8 };
+                let profile = HardwareProfile { l1: Some(cache), l2: None, l3: None };
+                assert_eq!(expected, equal_range_with_profile(&vec, &profile, |v| v.cmp(&x)));
+            }
+        }
+        assert_eq!(3, crate::count(&vec, &10));
+        assert_eq!(0, crate::count(&vec, &11));
+        assert_eq!(15..15, crate::equal_range(&vec, &9));
+    }
+}
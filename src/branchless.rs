@@ -0,0 +1,63 @@
+use std::cmp::Ordering;
+use std::hint::select_unpredictable;
+
+use crate::prefetch::prefetch;
+use crate::strategy::check;
+
+/// Internal branchless binary search algorithm, with `slice::binary_search_by` semantics.
+///
+/// Returns the leftmost match when there are duplicates.
+pub(crate) fn search_by<T, F>(collection: &[T], prefetch: bool, mut f: F) -> Result<usize, usize>
+where F: FnMut(&T) -> Ordering
+{
+    let idx = partition_point(collection, prefetch, |probe| f(probe) == Ordering::Less);
+    check(collection, idx, |probe| f(probe) == Ordering::Equal)
+}
+
+/// Branchless search for the first element that does not satisfy `pred`, as in `slice::partition_point`.
+///
+/// The loop always runs `log2(n)` times and moves its base with a conditional move rather than a branch,
+/// so the CPU never mispredicts on random queries. With `prefetch`, each step also prefetches both
+/// midpoints the next step could probe, so the memory access is in flight before it is needed.
+pub(crate) fn partition_point<T, P>(collection: &[T], prefetch_next: bool, mut pred: P) -> usize
+where P: FnMut(&T) -> bool
+{
+    if collection.is_empty() {
+        return 0;
+    }
+    let ptr = collection.as_ptr();
+    let mut base = 0;
+    let mut size = collection.len();
+    while size > 1 {
+        let half = size / 2;
+        let mid = base + half;
+        if prefetch_next {
+            let next_half = (size - half) / 2;
+            prefetch(ptr.wrapping_add(base + next_half));
+            prefetch(ptr.wrapping_add(mid + next_half));
+        }
+        base = select_unpredictable(pred(&collection[mid]), mid, base);
+        size -= half;
+    }
+    base + pred(&collection[base]) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{partition_point, search_by};
+
+    #[test]
+    fn test_matches_std() {
+        for len in [0, 1, 2, 3, 4, 5, 64, 100, 1023].iter() {
+            let vec: Vec<u32> = (0..*len).map(|x| x / 3 * 2).collect();
+            for x in 0..*len + 2 {
+                for prefetch in [false, true].iter() {
+                    let lower = vec.partition_point(|v| *v < x);
+                    assert_eq!(lower, partition_point(&vec, *prefetch, |v| *v < x));
+                    let expected = if vec.get(lower) == Some(&x) { Ok(lower) } else { Err(lower) };
+                    assert_eq!(expected, search_by(&vec, *prefetch, |v| v.cmp(&x)));
+                }
+            }
+        }
+    }
+}
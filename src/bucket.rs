@@ -0,0 +1,81 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+
+use crate::{HardwareProfile, Strategy};
+
+/// Which index [`searchsorted`] returns for values equal to a boundary, as in NumPy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    /// The index of the first boundary not less than the value (`boundaries[i - 1] < v <= boundaries[i]`).
+    Left,
+    /// The index of the first boundary greater than the value (`boundaries[i - 1] <= v < boundaries[i]`).
+    Right,
+}
+
+/// Finds, for every value, the index where it would be inserted into the sorted `boundaries`,
+/// like NumPy's `searchsorted`.
+///
+/// The search strategy is chosen once for the boundaries. If the values are themselves sorted,
+/// each search gallops from the previous value's index instead of starting over. Values that
+/// compare with nothing, such as `NaN`, are placed after every boundary, as NumPy does.
+pub fn searchsorted<T, Q>(boundaries: &[T], values: &[Q], side: Side) -> Vec<usize>
+where T: Borrow<Q>, Q: PartialOrd
+{
+    let profile = HardwareProfile::current_or_conservative();
+    let strategy = Strategy::select::<T>(&profile, boundaries.len());
+    let sorted = values.windows(2).all(|w| w[0] <= w[1]);
+    let mut previous = 0;
+    values
+        .iter()
+        .map(|value| {
+            let strategy = if sorted { Strategy::Exponential { hint: previous } } else { strategy };
+            previous = strategy.partition_point(boundaries, |boundary| before(boundary.borrow(), value, side));
+            previous
+        })
+        .collect()
+}
+
+/// Returns the bin of every value for the sorted bin edges `bins`, like NumPy's `digitize`.
+///
+/// With `right == false`, bin `i` holds `bins[i - 1] <= v < bins[i]`; with `right == true`, it
+/// holds `bins[i - 1] < v <= bins[i]`. Values below the first edge are in bin 0 and values at or
+/// above the last edge are in bin `bins.len()`.
+pub fn digitize<T, Q>(values: &[Q], bins: &[T], right: bool) -> Vec<usize>
+where T: Borrow<Q>, Q: PartialOrd
+{
+    searchsorted(bins, values, if right { Side::Left } else { Side::Right })
+}
+
+/// Returns whether `boundary` comes before the insertion point of `value` on the given side.
+fn before<Q>(boundary: &Q, value: &Q, side: Side) -> bool
+where Q: PartialOrd
+{
+    matches!(
+        (value.partial_cmp(boundary), side),
+        (Some(Ordering::Greater), _) | (None, _) | (Some(Ordering::Equal), Side::Right)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{digitize, searchsorted, Side};
+
+    #[test]
+    fn test_matches_numpy() {
+        let boundaries = [1.0, 2.0, 2.0, 3.0, 5.0];
+        let values = [0.5, 1.0, 2.0, 2.5, 5.0, 7.0, f64::NAN];
+        assert_eq!(vec![0, 0, 1, 3, 4, 5, 5], searchsorted(&boundaries, &values, Side::Left));
+        assert_eq!(vec![0, 1, 3, 3, 5, 5, 5], searchsorted(&boundaries, &values, Side::Right));
+
+        let unsorted = [7.0, 2.0, 0.5, 5.0, 2.5, 1.0];
+        assert_eq!(vec![5, 1, 0, 4, 3, 0], searchsorted(&boundaries, &unsorted, Side::Left));
+        assert_eq!(vec![5, 3, 0, 5, 3, 1], digitize(&unsorted, &boundaries, false));
+        assert_eq!(vec![5, 1, 0, 4, 3, 0], digitize(&unsorted, &boundaries, true));
+
+        let edges: Vec<u32> = (0..1000).map(|x| x * 10).collect();
+        let measurements: Vec<u32> = (0..5000).map(|x| (x * 7919) % 10_050).collect();
+        let expected: Vec<usize> = measurements.iter().map(|m| edges.partition_point(|e| e <= m)).collect();
+        assert_eq!(expected, digitize(&measurements, &edges, false));
+        assert!(searchsorted(&[] as &[u32], &[1, 2], Side::Left).iter().all(|idx| *idx == 0));
+    }
+}
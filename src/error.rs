@@ -0,0 +1,22 @@
+use std::error::Error;
+use std::fmt;
+
+/// Errors that can occur while preparing a cache-aware search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchError {
+    /// No provider could report cache parameters (e.g. CPUID leaf 4 is hidden on many VMs and containers).
+    CacheParametersUnavailable,
+    /// Cache parameters were reported, but none of them describe a data cache.
+    NoDataCache,
+}
+
+impl fmt::Display for SearchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SearchError::CacheParametersUnavailable => write!(f, "cache parameters are not available on this system"),
+            SearchError::NoDataCache => write!(f, "no data cache was reported"),
+        }
+    }
+}
+
+impl Error for SearchError {}
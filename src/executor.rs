@@ -0,0 +1,235 @@
+use std::borrow::Borrow;
+use std::hint::select_unpredictable;
+use std::mem::size_of;
+
+use crate::prefetch::prefetch;
+use crate::strategy::{check, jump_size, residency};
+use crate::HardwareProfile;
+
+/// Runs many independent searches over one sorted collection, interleaved to hide memory latency.
+///
+/// A single search over an array much larger than the last-level cache stalls on every probe. The
+/// executor keeps a group of searches in flight and advances them round-robin, one probe each,
+/// prefetching every search's next probe as soon as it is known. By the time the executor returns
+/// to a search, its cache line has usually arrived, so the misses of the whole group overlap.
+///
+/// Binary searches all take the same number of steps, so they run in lockstep (group prefetching).
+/// Jump searches take a varying number of steps, so each slot is refilled with the next query as
+/// soon as its search finishes (asynchronous memory access chaining).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchExecutor {
+    strategy: BatchStrategy,
+    group_size: Option<usize>,
+}
+
+/// The searches a [`BatchExecutor`] can interleave.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchStrategy {
+    /// Binary searches, run in lockstep.
+    Binary,
+    /// Jump searches, each slot refilled as soon as its search finishes.
+    Jump,
+}
+
+impl BatchExecutor {
+    /// Creates an executor that runs binary searches, with the group size derived from the hardware profile.
+    pub fn new() -> Self {
+        BatchExecutor { strategy: BatchStrategy::Binary, group_size: None }
+    }
+
+    /// Selects the search to run.
+    pub fn with_strategy(mut self, strategy: BatchStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Fixes the number of searches kept in flight instead of deriving it from the hardware profile.
+    pub fn with_group_size(mut self, group_size: usize) -> Self {
+        self.group_size = Some(group_size.max(1));
+        self
+    }
+
+    /// Returns the number of searches kept in flight for `len` elements of type `T` on the given hardware.
+    ///
+    /// Even within L1 and L2, eight searches are enough to cover the load-to-compare latency of
+    /// each step. Beyond L2 the group grows to 16, which is about how many L1 misses a core can
+    /// have outstanding at once.
+    pub fn group_size<T>(&self, profile: &HardwareProfile, len: usize) -> usize {
+        self.group_size.unwrap_or_else(|| match residency(profile, len.saturating_mul(size_of::<T>())) {
+            1 | 2 => 8,
+            _ => 16,
+        })
+    }
+
+    /// Looks up every query, returning the found index of each one or `None`.
+    pub fn find<T, Q>(&self, collection: &[T], queries: &[Q]) -> Vec<Option<usize>>
+    where T: Borrow<Q>, Q: Ord
+    {
+        self.search(collection, queries).into_iter().map(Result::ok).collect()
+    }
+
+    /// Looks up every query, with `slice::binary_search` semantics for each result.
+    pub fn search<T, Q>(&self, collection: &[T], queries: &[Q]) -> Vec<Result<usize, usize>>
+    where T: Borrow<Q>, Q: Ord
+    {
+        let profile = HardwareProfile::current_or_conservative();
+        let group_size = self.group_size::<T>(&profile, collection.len());
+        match self.strategy {
+            BatchStrategy::Binary => search_binary(collection, queries, group_size),
+            BatchStrategy::Jump => search_jump(collection, queries, group_size),
+        }
+    }
+}
+
+impl Default for BatchExecutor {
+    fn default() -> Self {
+        BatchExecutor::new()
+    }
+}
+
+/// Runs groups of branchless binary searches in lockstep, prefetching each one's next midpoint.
+fn search_binary<T, Q>(collection: &[T], queries: &[Q], group_size: usize) -> Vec<Result<usize, usize>>
+where T: Borrow<Q>, Q: Ord
+{
+    let group_size = group_size.min(queries.len()).max(1);
+    let mut results = Vec::with_capacity(queries.len());
+    let mut bases = vec![0; group_size];
+    for group in queries.chunks(group_size) {
+        let bases = &mut bases[..group.len()];
+        bases.iter_mut().for_each(|base| *base = 0);
+        lower_bounds(collection, group, bases);
+        results.extend(group.iter().zip(bases.iter()).map(|(item, idx)| check(collection, *idx, |probe| probe.borrow() == item)));
+    }
+    results
+}
+
+/// Runs one branchless lower bound search per item, one step of each at a time, storing the results in `bases`.
+fn lower_bounds<T, Q>(collection: &[T], items: &[Q], bases: &mut [usize])
+where T: Borrow<Q>, Q: Ord
+{
+    if collection.is_empty() {
+        return;
+    }
+    let ptr = collection.as_ptr();
+    let mut size = collection.len();
+    while size > 1 {
+        let half = size / 2;
+        let next_half = (size - half) / 2;
+        for (item, base) in items.iter().zip(bases.iter_mut()) {
+            let mid = *base + half;
+            *base = select_unpredictable(collection[mid].borrow() < item, mid, *base);
+            prefetch(ptr.wrapping_add(*base + next_half));
+        }
+        size -= half;
+    }
+    for (item, base) in items.iter().zip(bases.iter_mut()) {
+        *base += (collection[*base].borrow() < item) as usize;
+    }
+}
+
+/// The state of one in-flight jump search.
+#[derive(Clone, Copy)]
+struct JumpSlot {
+    /// Index of the query being searched for.
+    query: usize,
+    /// The next fence to probe, or the fence that ended the jump phase once `scanning` is set.
+    fence: usize,
+    scanning: bool,
+}
+
+/// Runs jump searches round-robin, refilling each slot with the next query as soon as its search finishes.
+fn search_jump<T, Q>(collection: &[T], queries: &[Q], group_size: usize) -> Vec<Result<usize, usize>>
+where T: Borrow<Q>, Q: Ord
+{
+    let ptr = collection.as_ptr();
+    let jump_size = jump_size(collection.len()).max(1);
+    let start = |query: usize| {
+        prefetch(ptr.wrapping_add(jump_size));
+        Some(JumpSlot { query, fence: jump_size, scanning: false })
+    };
+
+    let mut results = vec![Err(0); queries.len()];
+    let mut slots: Vec<Option<JumpSlot>> = (0..group_size.min(queries.len())).map(start).collect();
+    let mut next_query = slots.len();
+    let mut in_flight = slots.len();
+    while in_flight > 0 {
+        for slot in slots.iter_mut() {
+            let state = match slot {
+                Some(state) => state,
+                None => continue,
+            };
+            let item = &queries[state.query];
+            let left = state.fence - jump_size;
+            if !state.scanning {
+                if state.fence < collection.len() && collection[state.fence].borrow() < item {
+                    state.fence += jump_size;
+                    prefetch(ptr.wrapping_add(state.fence));
+                } else {
+                    state.scanning = true;
+                    prefetch(ptr.wrapping_add(left));
+                }
+                continue;
+            }
+            let right = (state.fence + 1).min(collection.len());
+            let idx = left + collection[left..right].partition_point(|probe| probe.borrow() < item);
+            results[state.query] = check(collection, idx, |probe| probe.borrow() == item);
+            if next_query < queries.len() {
+                *slot = start(next_query);
+                next_query += 1;
+            } else {
+                *slot = None;
+                in_flight -= 1;
+            }
+        }
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BatchExecutor, BatchStrategy};
+    use crate::{CacheLevel, HardwareProfile};
+
+    #[test]
+    fn test_executors_match_binary_search() {
+        for len in [0, 1, 2, 17, 1000].iter() {
+            let vec: Vec<u32> = (0..*len).map(|x| x / 2 * 3).collect();
+            let queries: Vec<u32> = (0..777).map(|x| (x * 7919) % (*len * 2 + 3)).collect();
+            for group_size in [1, 3, 16].iter() {
+                for strategy in [BatchStrategy::Binary, BatchStrategy::Jump].iter() {
+                    let executor = BatchExecutor::new().with_strategy(*strategy).with_group_size(*group_size);
+                    let results = executor.search(&vec, &queries);
+                    for (query, result) in queries.iter().zip(results.iter()) {
+                        let lower = vec.partition_point(|v| v < query);
+                        match result {
+                            Ok(idx) => assert_eq!(query, &vec[*idx]),
+                            Err(idx) => assert_eq!(lower, *idx),
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_group_size_larger_than_queries() {
+        let vec: Vec<u32> = (0..100).collect();
+        for strategy in [BatchStrategy::Binary, BatchStrategy::Jump].iter() {
+            let executor = BatchExecutor::new().with_strategy(*strategy).with_group_size(1 << 40);
+            assert_eq!(vec![Ok(1), Ok(2), Err(100)], executor.search(&vec, &[1, 2, 500]));
+            assert!(executor.search(&vec, &[] as &[u32]).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_group_size_follows_residency() {
+        let cache = |size| Some(CacheLevel { size, line_size: 64, associativity: 8 });
+        let profile = HardwareProfile { l1: cache(1 << 15), l2: cache(1 << 20), l3: cache(1 << 25) };
+        let executor = BatchExecutor::new();
+        assert_eq!(8, executor.group_size::<u64>(&profile, 1000));
+        assert_eq!(8, executor.group_size::<u64>(&profile, 100_000));
+        assert_eq!(16, executor.group_size::<u64>(&profile, 1_000_000));
+        assert_eq!(16, executor.group_size::<u64>(&profile, 100_000_000));
+        assert_eq!(5, executor.with_group_size(5).group_size::<u64>(&profile, 1000));
+    }
+}
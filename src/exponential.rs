@@ -0,0 +1,82 @@
+use std::cmp::Ordering;
+use std::ops::Range;
+
+/// Internal exponential (galloping) search algorithm, starting from index `start`.
+///
+/// Costs `O(log d)` probes where `d` is the distance between `start` and the target, so it is
+/// cheap when the target is known to be near the front or near a previous hit.
+pub(crate) fn search_by<T, F>(collection: &[T], start: usize, mut f: F) -> Result<usize, usize>
+where F: FnMut(&T) -> Ordering
+{
+    let window = gallop(collection, start, |probe| f(probe) == Ordering::Less);
+    match collection[window.clone()].binary_search_by(f) {
+        Ok(idx) => Ok(window.start + idx),
+        Err(idx) => Err(window.start + idx),
+    }
+}
+
+/// Exponential search for the first element that does not satisfy `pred`, as in `slice::partition_point`.
+pub(crate) fn partition_point<T, P>(collection: &[T], start: usize, mut pred: P) -> usize
+where P: FnMut(&T) -> bool
+{
+    let window = gallop(collection, start, &mut pred);
+    window.start + collection[window].partition_point(pred)
+}
+
+/// Gallops outwards from `start` in doubling steps, returning a window that contains the partition point of `pred`.
+///
+/// The window always includes the first element that does not satisfy `pred`, if one lies within it,
+/// so an exact match at the partition point can be found by searching the window alone.
+fn gallop<T, P>(collection: &[T], start: usize, mut pred: P) -> Range<usize>
+where P: FnMut(&T) -> bool
+{
+    if collection.is_empty() {
+        return 0..0;
+    }
+    let start = start.min(collection.len() - 1);
+    let mut step = 1;
+    if pred(&collection[start]) {
+        let mut lo = start + 1;
+        loop {
+            let probe = start + step;
+            if probe >= collection.len() {
+                return lo..collection.len();
+            }
+            if !pred(&collection[probe]) {
+                return lo..probe + 1;
+            }
+            lo = probe + 1;
+            step *= 2;
+        }
+    } else {
+        let mut hi = start + 1;
+        loop {
+            if step > start {
+                return 0..hi;
+            }
+            let probe = start - step;
+            if pred(&collection[probe]) {
+                return probe + 1..hi;
+            }
+            hi = probe + 1;
+            step *= 2;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{partition_point, search_by};
+    use crate::testing::{assert_lower_bound_matches, assert_search_matches, sorted_fixtures};
+
+    #[test]
+    fn test_gallop_from_any_start() {
+        for vec in sorted_fixtures() {
+            for start in [0, 1, 7, 100, 199, 500].iter() {
+                assert_lower_bound_matches(&vec, |x| partition_point(&vec, *start, |v| *v < x));
+                assert_search_matches(&vec, |x| search_by(&vec, *start, |v| v.cmp(&x)));
+            }
+        }
+        assert_eq!(Err(0), search_by(&[] as &[u32], 3, |v| v.cmp(&1)));
+    }
+}
@@ -0,0 +1,139 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+
+use crate::{search_by, search_jump_by};
+
+/// Method-call access to the crate's searches for sorted contiguous collections.
+///
+/// Implemented for `[T]`, so it is available on anything that derefs to a slice
+/// (`Vec<T>`, arrays, `Box<[T]>`, `Arc<[T]>`, memory-mapped buffers, ...), and for `VecDeque<T>`.
+pub trait SmartSearchExt<T> {
+    /// See [`search_by`](crate::search_by).
+    fn smart_search_by<F>(&self, f: F) -> Result<usize, usize>
+    where F: FnMut(&T) -> Ordering;
+
+    /// See [`search_jump_by`](crate::search_jump_by).
+    fn smart_search_jump_by<F>(&self, f: F) -> Result<usize, usize>
+    where F: FnMut(&T) -> Ordering;
+
+    /// See [`search`](crate::search).
+    fn smart_search<Q>(&self, item: &Q) -> Result<usize, usize>
+    where T: Borrow<Q>, Q: ?Sized + Ord
+    {
+        self.smart_search_by(|probe| probe.borrow().cmp(item))
+    }
+
+    /// See [`search_by_key`](crate::search_by_key).
+    fn smart_search_by_key<K, F>(&self, key: &K, mut f: F) -> Result<usize, usize>
+    where K: Ord, F: FnMut(&T) -> K
+    {
+        self.smart_search_by(|probe| f(probe).cmp(key))
+    }
+
+    /// See [`find`](crate::find).
+    fn smart_find<Q>(&self, item: &Q) -> Option<usize>
+    where T: Borrow<Q>, Q: ?Sized + Ord
+    {
+        self.smart_search(item).ok()
+    }
+
+    /// See [`find_by`](crate::find_by).
+    fn smart_find_by<F>(&self, f: F) -> Option<usize>
+    where F: FnMut(&T) -> Ordering
+    {
+        self.smart_search_by(f).ok()
+    }
+
+    /// See [`find_by_key`](crate::find_by_key).
+    fn smart_find_by_key<K, F>(&self, key: &K, f: F) -> Option<usize>
+    where K: Ord, F: FnMut(&T) -> K
+    {
+        self.smart_search_by_key(key, f).ok()
+    }
+
+    /// See [`find_jump`](crate::find_jump).
+    fn smart_find_jump<Q>(&self, item: &Q) -> Option<usize>
+    where T: Borrow<Q>, Q: ?Sized + Ord
+    {
+        self.smart_search_jump_by(|probe| probe.borrow().cmp(item)).ok()
+    }
+}
+
+impl<T> SmartSearchExt<T> for [T] {
+    fn smart_search_by<F>(&self, f: F) -> Result<usize, usize>
+    where F: FnMut(&T) -> Ordering
+    {
+        search_by(self, f)
+    }
+
+    fn smart_search_jump_by<F>(&self, f: F) -> Result<usize, usize>
+    where F: FnMut(&T) -> Ordering
+    {
+        search_jump_by(self, f)
+    }
+}
+
+impl<T> SmartSearchExt<T> for VecDeque<T> {
+    fn smart_search_by<F>(&self, mut f: F) -> Result<usize, usize>
+    where F: FnMut(&T) -> Ordering
+    {
+        let (half, offset) = half_for(self, &mut f);
+        offset_result(search_by(half, f), offset)
+    }
+
+    fn smart_search_jump_by<F>(&self, mut f: F) -> Result<usize, usize>
+    where F: FnMut(&T) -> Ordering
+    {
+        let (half, offset) = half_for(self, &mut f);
+        offset_result(search_jump_by(half, f), offset)
+    }
+}
+
+/// Returns whichever half of the deque could contain the target, and that half's offset into the deque.
+fn half_for<'a, T, F>(deque: &'a VecDeque<T>, f: &mut F) -> (&'a [T], usize)
+where F: FnMut(&T) -> Ordering
+{
+    let (front, back) = deque.as_slices();
+    match front.last() {
+        Some(last) if f(last) != Ordering::Less => (front, 0),
+        _ => (back, front.len()),
+    }
+}
+
+/// Shifts both the found index and the insertion point of a half's result by `offset`.
+fn offset_result(result: Result<usize, usize>, offset: usize) -> Result<usize, usize> {
+    result.map(|idx| offset + idx).map_err(|idx| offset + idx)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use std::sync::Arc;
+
+    use super::SmartSearchExt;
+
+    #[test]
+    fn test_smart_find_containers() {
+        let array = [1, 3, 5, 7, 9, 11, 13];
+        assert_eq!(Some(2), array.smart_find(&5));
+        assert_eq!(Some(1), array[2..].smart_find_jump(&7));
+        assert_eq!(Some(6), array.to_vec().smart_find(&13));
+        assert_eq!(Some(0), Box::<[i32]>::from(&array[..]).smart_find(&1));
+        assert_eq!(None, Arc::<[i32]>::from(&array[..]).smart_find(&4));
+
+        let mut deque: VecDeque<i32> = array[3..].iter().copied().collect();
+        for x in array[..3].iter().rev() {
+            deque.push_front(*x);
+        }
+        assert!(!deque.as_slices().1.is_empty());
+        for (idx, x) in array.iter().enumerate() {
+            assert_eq!(Some(idx), deque.smart_find(x));
+            assert_eq!(Some(idx), deque.smart_find_jump(x));
+            assert_eq!(Some(idx), deque.smart_find_by_key(&(x * 2), |v| v * 2));
+            assert_eq!(Err(idx), deque.smart_search(&(x - 1)));
+        }
+        assert_eq!(None, deque.smart_find(&8));
+        assert_eq!(Err(7), deque.smart_search(&20));
+    }
+}
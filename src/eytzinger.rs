@@ -0,0 +1,148 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::mem::size_of;
+
+use crate::aligned::AlignedSlots;
+use crate::prefetch::prefetch;
+use crate::strategy::DEFAULT_LINE_SIZE;
+use crate::HardwareProfile;
+
+/// A read-only search index that stores a sorted collection in Eytzinger (BFS) order.
+///
+/// The root is at position 1 and the children of position `k` are at `2k` and `2k + 1`, so the
+/// top levels of the implicit tree that every search walks through share a handful of cache lines.
+/// Storage is aligned to the detected cache line size, which puts the `B` descendants of a node
+/// `log2(B)` levels down (where `B` elements fit in a line) on a single line; each step of the
+/// search prefetches that line, hiding most of the memory latency of the levels below.
+///
+/// Lookups return indices into the original sorted collection.
+#[derive(Debug, Clone)]
+pub struct EytzingerIndex<T> {
+    /// The unused slot 0, then the elements in BFS order.
+    slots: AlignedSlots<T>,
+    /// The sorted index of the element at each BFS position.
+    ranks: Vec<usize>,
+    /// Number of elements per cache line, rounded down to a power of two.
+    stride: usize,
+}
+
+impl<T> EytzingerIndex<T>
+where T: Clone
+{
+    /// Builds an index from a sorted collection.
+    pub fn new(sorted: &[T]) -> Self {
+        let line_size = HardwareProfile::current_or_conservative().line_size().unwrap_or(DEFAULT_LINE_SIZE);
+        let element_size = size_of::<T>().max(1);
+        let stride = prev_power_of_two((line_size / element_size).max(1));
+        let first = match sorted.first() {
+            Some(first) => first,
+            None => return EytzingerIndex { slots: AlignedSlots::empty(), ranks: vec![0], stride },
+        };
+        let slots = AlignedSlots::new(sorted.len() + 1, first, line_size);
+        let mut ranks = vec![0; sorted.len() + 1];
+        let mut index = EytzingerIndex { slots, ranks: Vec::new(), stride };
+        let mut next = 0;
+        index.fill(sorted, &mut ranks, &mut next, 1);
+        index.ranks = ranks;
+        index
+    }
+
+    /// Places `sorted[next..]` into the subtree rooted at `k` by in-order traversal.
+    fn fill(&mut self, sorted: &[T], ranks: &mut [usize], next: &mut usize, k: usize) {
+        if k <= sorted.len() {
+            self.fill(sorted, ranks, next, 2 * k);
+            self.slots[k] = sorted[*next].clone();
+            ranks[k] = *next;
+            *next += 1;
+            self.fill(sorted, ranks, next, 2 * k + 1);
+        }
+    }
+}
+
+impl<T> EytzingerIndex<T> {
+    /// Returns the number of indexed elements.
+    pub fn len(&self) -> usize {
+        self.ranks.len() - 1
+    }
+
+    /// Returns `true` if the index holds no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Looks up an element, returning its index in the original sorted collection.
+    pub fn find<Q>(&self, item: &Q) -> Option<usize>
+    where T: Borrow<Q>, Q: ?Sized + Ord
+    {
+        self.search(item).ok()
+    }
+
+    /// Like [`EytzingerIndex::find`], but returns the insertion point on a miss, as in `slice::binary_search`.
+    pub fn search<Q>(&self, item: &Q) -> Result<usize, usize>
+    where T: Borrow<Q>, Q: ?Sized + Ord
+    {
+        self.search_by(|probe| probe.borrow().cmp(item))
+    }
+
+    /// Like [`EytzingerIndex::search`], but with a comparator that orders a probed element relative to the target.
+    pub fn search_by<F>(&self, mut f: F) -> Result<usize, usize>
+    where F: FnMut(&T) -> Ordering
+    {
+        let k = self.lower_bound_position(&mut f);
+        if k == 0 {
+            return Err(self.len());
+        }
+        match f(&self.slots[k]) {
+            Ordering::Equal => Ok(self.ranks[k]),
+            _ => Err(self.ranks[k]),
+        }
+    }
+
+    /// Returns the index in the original sorted collection of the first element not less than `item`.
+    pub fn lower_bound<Q>(&self, item: &Q) -> usize
+    where T: Borrow<Q>, Q: ?Sized + Ord
+    {
+        match self.lower_bound_position(|probe| probe.borrow().cmp(item)) {
+            0 => self.len(),
+            k => self.ranks[k],
+        }
+    }
+
+    /// Returns the BFS position of the first element not less than the target, or 0 if there is none.
+    fn lower_bound_position<F>(&self, mut f: F) -> usize
+    where F: FnMut(&T) -> Ordering
+    {
+        let base = self.slots.as_ptr();
+        let mut k = 1;
+        while k <= self.len() {
+            prefetch(base.wrapping_add(k * self.stride));
+            k = 2 * k + (f(&self.slots[k]) == Ordering::Less) as usize;
+        }
+        // Every right turn appended a 1 bit; undo them and the final left turn to reach the answer.
+        k >> (k.trailing_ones() + 1)
+    }
+}
+
+/// Rounds down to the nearest power of two, with `prev_power_of_two(0) == 0`.
+fn prev_power_of_two(n: usize) -> usize {
+    if n == 0 { 0 } else { 1 << (usize::BITS - 1 - n.leading_zeros()) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EytzingerIndex;
+    use crate::testing::{assert_lower_bound_matches, assert_search_matches, sorted_fixtures};
+
+    #[test]
+    fn test_matches_sorted_search() {
+        for vec in sorted_fixtures() {
+            let index = EytzingerIndex::new(&vec);
+            assert_eq!(vec.len(), index.len());
+            assert_lower_bound_matches(&vec, |x| index.lower_bound(&x));
+            assert_search_matches(&vec, |x| index.search(&x));
+        }
+
+        let strings: Vec<String> = ["ant", "bee", "cat"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(Some(1), EytzingerIndex::new(&strings).find("bee"));
+    }
+}
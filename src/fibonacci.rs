@@ -0,0 +1,65 @@
+use std::cmp::Ordering;
+
+use crate::strategy::check;
+
+/// Internal Fibonacci search algorithm.
+///
+/// Like binary search, but the range is split at Fibonacci numbers rather than halved, so index
+/// arithmetic needs only additions and subtractions, and consecutive probes land closer together
+/// (the range shrinks by about 0.62 or 0.38 rather than 0.5 per probe).
+pub(crate) fn search_by<T, F>(collection: &[T], mut f: F) -> Result<usize, usize>
+where F: FnMut(&T) -> Ordering
+{
+    let idx = partition_point(collection, |probe| f(probe) == Ordering::Less);
+    check(collection, idx, |probe| f(probe) == Ordering::Equal)
+}
+
+/// Fibonacci search for the first element that does not satisfy `pred`, as in `slice::partition_point`.
+pub(crate) fn partition_point<T, P>(collection: &[T], mut pred: P) -> usize
+where P: FnMut(&T) -> bool
+{
+    // Positions past the end act as padding that never satisfies `pred`.
+    let mut probe = |idx: usize| idx < collection.len() && pred(&collection[idx]);
+
+    // The answer lies in `lo..=lo + size`, where `size` is a Fibonacci number and `shorter` and
+    // `shortest` are the two before it.
+    let (mut shortest, mut shorter, mut size) = (0, 1, 1);
+    while size < collection.len() {
+        shortest = shorter;
+        shorter = size;
+        size = shorter + shortest;
+    }
+    let mut lo = 0;
+    while size > 1 {
+        if probe(lo + shortest - 1) {
+            // The answer is in the longer part: step down one Fibonacci number.
+            lo += shortest;
+            size = shorter;
+            shorter = shortest;
+            shortest = size - shorter;
+        } else {
+            // The answer is in the shorter part: step down two.
+            size = shortest;
+            shorter -= shortest;
+            shortest = size - shorter;
+        }
+    }
+    if size == 1 && probe(lo) {
+        lo += 1;
+    }
+    lo
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{partition_point, search_by};
+    use crate::testing::{assert_lower_bound_matches, assert_search_matches, sorted_fixtures};
+
+    #[test]
+    fn test_matches_sorted_search() {
+        for vec in sorted_fixtures() {
+            assert_lower_bound_matches(&vec, |x| partition_point(&vec, |v| *v < x));
+            assert_search_matches(&vec, |x| search_by(&vec, |v| v.cmp(&x)));
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{OnceLock, RwLock};
+
+use crate::SearchError;
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod cpuid;
+mod sysfs;
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+pub use cpuid::CpuidProvider;
+pub use sysfs::SysfsProvider;
+
+static DETECTED: OnceLock<Result<HardwareProfile, SearchError>> = OnceLock::new();
+static OVERRIDE: RwLock<Option<HardwareProfile>> = RwLock::new(None);
+/// Whether `OVERRIDE` holds a profile, so lookups without an override never touch the lock.
+static OVERRIDDEN: AtomicBool = AtomicBool::new(false);
+
+/// A source of cache hierarchy information.
+pub trait CacheInfoProvider {
+    /// Detects the data cache hierarchy.
+    fn detect(&self) -> Result<HardwareProfile, SearchError>;
+}
+
+/// Always reports [`HardwareProfile::conservative`], for targets where nothing can be detected.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConservativeProvider;
+
+impl CacheInfoProvider for ConservativeProvider {
+    fn detect(&self) -> Result<HardwareProfile, SearchError> {
+        Ok(HardwareProfile::conservative())
+    }
+}
+
+/// Geometry of a single data (or unified) cache level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheLevel {
+    /// Total capacity in bytes.
+    pub size: usize,
+    /// Coherency line size in bytes.
+    pub line_size: usize,
+    /// Number of ways, or `0` if the cache is fully associative.
+    pub associativity: usize,
+}
+
+/// The data cache hierarchy that the cache-aware searches are tuned against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HardwareProfile {
+    pub l1: Option<CacheLevel>,
+    pub l2: Option<CacheLevel>,
+    pub l3: Option<CacheLevel>,
+}
+
+impl HardwareProfile {
+    /// Returns the profile used by `find`: the override if one is set, otherwise the detected hardware.
+    ///
+    /// Detection runs at most once per process; the result (including a failure) is cached.
+    pub fn current() -> Result<HardwareProfile, SearchError> {
+        if OVERRIDDEN.load(Ordering::Relaxed) {
+            if let Some(profile) = *OVERRIDE.read().unwrap_or_else(|e| e.into_inner()) {
+                return Ok(profile);
+            }
+        }
+        *DETECTED.get_or_init(HardwareProfile::detect)
+    }
+
+    /// Like [`HardwareProfile::current`], but substitutes [`HardwareProfile::conservative`] if detection failed.
+    pub(crate) fn current_or_conservative() -> HardwareProfile {
+        HardwareProfile::current().unwrap_or_else(|_| HardwareProfile::conservative())
+    }
+
+    /// Replaces the profile returned by [`HardwareProfile::current`], or restores detection with `None`.
+    ///
+    /// Intended for tests and benchmarks that need a deterministic cache hierarchy.
+    pub fn set_override(profile: Option<HardwareProfile>) {
+        let mut guard = OVERRIDE.write().unwrap_or_else(|e| e.into_inner());
+        *guard = profile;
+        OVERRIDDEN.store(profile.is_some(), Ordering::Relaxed);
+    }
+
+    /// Queries the available providers for the data cache hierarchy, bypassing the process-wide cache.
+    ///
+    /// CPUID is tried first on x86, then sysfs on Linux; the last provider's error is returned if none succeed.
+    pub fn detect() -> Result<HardwareProfile, SearchError> {
+        let result = Err(SearchError::CacheParametersUnavailable);
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        let result = result.or_else(|_| CpuidProvider.detect());
+        #[cfg(target_os = "linux")]
+        let result = result.or_else(|_| SysfsProvider::default().detect());
+        result
+    }
+
+    /// A small, widely applicable hierarchy used when detection fails.
+    pub const fn conservative() -> HardwareProfile {
+        HardwareProfile {
+            l1: Some(CacheLevel { size: 32 << 10, line_size: 64, associativity: 8 }),
+            l2: Some(CacheLevel { size: 256 << 10, line_size: 64, associativity: 8 }),
+            l3: None,
+        }
+    }
+
+    /// Builds a profile from `(level, cache)` pairs, failing if no level 1-3 cache is present.
+    fn from_levels<I>(levels: I) -> Result<HardwareProfile, SearchError>
+    where I: IntoIterator<Item = (u8, CacheLevel)>
+    {
+        let mut profile = HardwareProfile::default();
+        for (level, cache) in levels {
+            match level {
+                1 => profile.l1 = Some(cache),
+                2 => profile.l2 = Some(cache),
+                3 => profile.l3 = Some(cache),
+                _ => {}
+            }
+        }
+        if profile.levels().next().is_none() {
+            return Err(SearchError::NoDataCache);
+        }
+        Ok(profile)
+    }
+
+    /// Iterates over the known cache levels, from closest to the core outwards.
+    pub fn levels(&self) -> impl Iterator<Item = CacheLevel> {
+        IntoIterator::into_iter([self.l1, self.l2, self.l3]).flatten()
+    }
+
+    /// Returns the smallest line size across all known cache levels.
+    pub fn line_size(&self) -> Option<usize> {
+        self.levels().map(|c| c.line_size).min()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CacheLevel, HardwareProfile};
+
+    #[test]
+    fn test_line_size() {
+        let profile = HardwareProfile {
+            l1: None,
+            l2: Some(CacheLevel { size: 1 << 20, line_size: 128, associativity: 16 }),
+            l3: Some(CacheLevel { size: 1 << 25, line_size: 64, associativity: 0 }),
+        };
+        assert_eq!(Some(64), profile.line_size());
+        assert_eq!(2, profile.levels().count());
+        assert_eq!(None, HardwareProfile::default().line_size());
+    }
+
+    /// The override is process-wide, so setting and clearing it is checked in this one test.
+    #[test]
+    fn test_override_round_trip() {
+        let detected = HardwareProfile::current();
+        let profile = HardwareProfile {
+            l1: Some(CacheLevel { size: 1 << 12, line_size: 128, associativity: 2 }),
+            l2: None,
+            l3: None,
+        };
+        HardwareProfile::set_override(Some(profile));
+        assert_eq!(Ok(profile), HardwareProfile::current());
+        assert_eq!(profile, HardwareProfile::current_or_conservative());
+        HardwareProfile::set_override(None);
+        assert_eq!(detected, HardwareProfile::current());
+    }
+}
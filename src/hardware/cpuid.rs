@@ -0,0 +1,26 @@
+use raw_cpuid::{CacheType, CpuId};
+
+use super::{CacheInfoProvider, CacheLevel, HardwareProfile};
+use crate::SearchError;
+
+/// Reads the cache hierarchy from the CPUID deterministic cache parameters leaf.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CpuidProvider;
+
+impl CacheInfoProvider for CpuidProvider {
+    fn detect(&self) -> Result<HardwareProfile, SearchError> {
+        let parameters = CpuId::new()
+            .get_cache_parameters()
+            .ok_or(SearchError::CacheParametersUnavailable)?
+            .filter(|c| c.cache_type() == CacheType::Data || c.cache_type() == CacheType::Unified)
+            .map(|c| {
+                let level = CacheLevel {
+                    size: c.associativity() * c.physical_line_partitions() * c.coherency_line_size() * c.sets(),
+                    line_size: c.coherency_line_size(),
+                    associativity: if c.is_fully_associative() { 0 } else { c.associativity() },
+                };
+                (c.level(), level)
+            });
+        HardwareProfile::from_levels(parameters)
+    }
+}
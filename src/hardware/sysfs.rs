@@ -0,0 +1,125 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{CacheInfoProvider, CacheLevel, HardwareProfile};
+use crate::SearchError;
+
+/// Reads the cache hierarchy from Linux sysfs (`/sys/devices/system/cpu/cpu*/cache/index*`).
+#[derive(Debug, Clone)]
+pub struct SysfsProvider {
+    root: PathBuf,
+}
+
+impl SysfsProvider {
+    /// Creates a provider that reads from `root` instead of `/sys/devices/system/cpu`.
+    pub fn new<P: Into<PathBuf>>(root: P) -> Self {
+        SysfsProvider { root: root.into() }
+    }
+
+    /// Returns the cache directory of the lowest-numbered CPU that has one.
+    fn cache_dir(&self) -> Option<PathBuf> {
+        let mut cpus: Vec<(usize, PathBuf)> = fs::read_dir(&self.root)
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let name = entry.file_name();
+                let id = name.to_str()?.strip_prefix("cpu")?.parse().ok()?;
+                Some((id, entry.path().join("cache")))
+            })
+            .filter(|(_, path)| path.is_dir())
+            .collect();
+        cpus.sort();
+        cpus.into_iter().next().map(|(_, path)| path)
+    }
+}
+
+impl Default for SysfsProvider {
+    fn default() -> Self {
+        SysfsProvider::new("/sys/devices/system/cpu")
+    }
+}
+
+impl CacheInfoProvider for SysfsProvider {
+    fn detect(&self) -> Result<HardwareProfile, SearchError> {
+        let cache_dir = self.cache_dir().ok_or(SearchError::CacheParametersUnavailable)?;
+        let entries = fs::read_dir(cache_dir).map_err(|_| SearchError::CacheParametersUnavailable)?;
+        let levels = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_name().to_str().is_some_and(|name| name.starts_with("index")))
+            .filter_map(|entry| parse_index(&entry.path()));
+        HardwareProfile::from_levels(levels)
+    }
+}
+
+/// Parses a single `index*` directory, skipping instruction caches and incomplete entries.
+fn parse_index(dir: &Path) -> Option<(u8, CacheLevel)> {
+    let read = |name: &str| fs::read_to_string(dir.join(name)).ok().map(|s| s.trim().to_string());
+    match read("type")?.as_str() {
+        "Data" | "Unified" => {}
+        _ => return None,
+    }
+    let level = CacheLevel {
+        size: parse_size(&read("size")?)?,
+        line_size: read("coherency_line_size")?.parse().ok()?,
+        associativity: read("ways_of_associativity").and_then(|s| s.parse().ok()).unwrap_or(0),
+    };
+    Some((read("level")?.parse().ok()?, level))
+}
+
+/// Parses sysfs sizes such as `48K`, `2048K` or `1M` into bytes.
+fn parse_size(size: &str) -> Option<usize> {
+    let (digits, multiplier) = match size.chars().last()? {
+        'K' => (&size[..size.len() - 1], 1 << 10),
+        'M' => (&size[..size.len() - 1], 1 << 20),
+        'G' => (&size[..size.len() - 1], 1 << 30),
+        _ => (size, 1),
+    };
+    digits.parse::<usize>().ok().map(|n| n * multiplier)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{parse_size, SysfsProvider};
+    use crate::hardware::{CacheInfoProvider, CacheLevel};
+
+    #[test]
+    fn test_detect_fake_tree() {
+        let root = std::env::temp_dir().join(format!("smart_search_sysfs_{}", std::process::id()));
+        let indices = [
+            ("index0", "1", "Data", "48K", "64", "12"),
+            ("index1", "1", "Instruction", "32K", "64", "8"),
+            ("index2", "2", "Unified", "2048K", "64", "16"),
+            ("index3", "3", "Unified", "30M", "128", "0"),
+        ];
+        for (index, level, kind, size, line, ways) in indices.iter() {
+            let dir = root.join("cpu0/cache").join(index);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("level"), format!("{}\n", level)).unwrap();
+            fs::write(dir.join("type"), format!("{}\n", kind)).unwrap();
+            fs::write(dir.join("size"), format!("{}\n", size)).unwrap();
+            fs::write(dir.join("coherency_line_size"), format!("{}\n", line)).unwrap();
+            fs::write(dir.join("ways_of_associativity"), format!("{}\n", ways)).unwrap();
+        }
+        fs::create_dir_all(root.join("cpufreq")).unwrap();
+
+        let profile = SysfsProvider::new(&root).detect();
+        fs::remove_dir_all(&root).unwrap();
+
+        let profile = profile.unwrap();
+        assert_eq!(Some(CacheLevel { size: 48 << 10, line_size: 64, associativity: 12 }), profile.l1);
+        assert_eq!(Some(CacheLevel { size: 2 << 20, line_size: 64, associativity: 16 }), profile.l2);
+        assert_eq!(Some(CacheLevel { size: 30 << 20, line_size: 128, associativity: 0 }), profile.l3);
+        assert!(SysfsProvider::new(root).detect().is_err());
+    }
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(Some(49152), parse_size("48K"));
+        assert_eq!(Some(1 << 20), parse_size("1M"));
+        assert_eq!(Some(512), parse_size("512"));
+        assert_eq!(None, parse_size("K"));
+        assert_eq!(None, parse_size(""));
+    }
+}
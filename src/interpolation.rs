@@ -0,0 +1,131 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+
+use crate::strategy::check;
+
+/// Keys that can be mapped onto a number line, which interpolation search uses to guess positions.
+///
+/// The mapping must preserve order: `a < b` implies `a.position() <= b.position()`. It does not
+/// need to be exact, so 64-bit and 128-bit integers are simply rounded to the nearest `f64`.
+pub trait Interpolate {
+    /// Returns this key's position on the number line.
+    fn position(&self) -> f64;
+}
+
+macro_rules! impl_interpolate {
+    ($($t:ty),*) => {
+        $(
+            impl Interpolate for $t {
+                fn position(&self) -> f64 {
+                    *self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_interpolate!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+/// Internal interpolation search algorithm, with `slice::binary_search` semantics.
+pub(crate) fn search<T, Q>(collection: &[T], item: &Q) -> Result<usize, usize>
+where T: Borrow<Q>, Q: ?Sized + Interpolate + PartialOrd
+{
+    check(collection, lower_bound(collection, item), |probe: &T| probe.borrow() == item)
+}
+
+/// Interpolation search for the first element that is not less than `item`.
+///
+/// Uniformly distributed keys need `O(log log n)` probes. Skewed keys can make interpolation
+/// degrade towards a linear scan, so after a budget of `O(log log n)` probes the remaining window
+/// is finished with binary search instead.
+pub(crate) fn lower_bound<T, Q>(collection: &[T], item: &Q) -> usize
+where T: Borrow<Q>, Q: ?Sized + Interpolate + PartialOrd
+{
+    let target = item.position();
+    let mut budget = 2 * log2(log2(collection.len())) + 4;
+    let mut lo = 0;
+    let mut hi = collection.len();
+    while lo < hi {
+        if collection[lo].borrow() >= item {
+            return lo;
+        }
+        if collection[hi - 1].borrow() < item {
+            return hi;
+        }
+        if budget == 0 {
+            return lo + collection[lo..hi].partition_point(|probe| probe.borrow() < item);
+        }
+        budget -= 1;
+        // Here collection[lo] < item <= collection[hi - 1], so the answer lies in (lo, hi - 1].
+        let first = collection[lo].borrow().position();
+        let last = collection[hi - 1].borrow().position();
+        let fraction = (target - first) / (last - first);
+        let offset = if fraction.is_finite() { (fraction * (hi - 1 - lo) as f64) as usize } else { 0 };
+        let probe = (lo + offset).max(lo + 1).min(hi - 1);
+        if collection[probe].borrow() < item {
+            lo = probe + 1;
+        } else {
+            hi = probe;
+        }
+    }
+    lo
+}
+
+/// Samples the quartiles of the collection to check whether its keys grow roughly linearly with their index.
+pub(crate) fn looks_uniform<T, Q>(collection: &[T]) -> bool
+where T: Borrow<Q>, Q: ?Sized + Interpolate
+{
+    let len = collection.len();
+    if len < 4 {
+        return false;
+    }
+    let first = collection[0].borrow().position();
+    let last = collection[len - 1].borrow().position();
+    let span = last - first;
+    if span <= 0.0 || !span.is_finite() {
+        return false;
+    }
+    (1..4).all(|quartile| {
+        let idx = len * quartile / 4;
+        let expected = first + span * idx as f64 / (len - 1) as f64;
+        (collection[idx].borrow().position() - expected).abs() <= span / 8.0
+    })
+}
+
+/// Compares keys that are only partially ordered, treating incomparable probes (e.g. `NaN`) as smaller.
+pub(crate) fn compare<Q>(probe: &Q, item: &Q) -> Ordering
+where Q: ?Sized + PartialOrd
+{
+    probe.partial_cmp(item).unwrap_or(Ordering::Less)
+}
+
+/// Rounded-up base-2 logarithm, with `log2(0) == 0`.
+fn log2(n: usize) -> usize {
+    (usize::BITS - n.leading_zeros()) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{looks_uniform, search};
+
+    #[test]
+    fn test_search_uniform_and_skewed() {
+        let uniform: Vec<u64> = (0..10_000).map(|x| x * 13 + 5).collect();
+        let skewed: Vec<u64> = (0..64).map(|x| 1u64 << x).chain(std::iter::once(u64::MAX)).collect();
+        let floats: Vec<f64> = (0..1000).map(|x| (x as f64).sqrt()).collect();
+        assert!(looks_uniform::<_, u64>(&uniform));
+        assert!(!looks_uniform::<_, u64>(&skewed));
+        for x in (0..130_100).step_by(3) {
+            assert_eq!(uniform.binary_search(&x), search(&uniform, &x));
+        }
+        for x in skewed.iter() {
+            assert_eq!(skewed.binary_search(x), search(&skewed, x));
+            assert_eq!(skewed.binary_search(&x.wrapping_add(1)), search(&skewed, &x.wrapping_add(1)));
+        }
+        for x in floats.iter() {
+            assert_eq!(Some(*x), search(&floats, x).ok().map(|idx| floats[idx]));
+        }
+        assert_eq!(Err(0), search(&[] as &[u64], &3));
+        assert_eq!(Err(1000), search(&floats, &f64::INFINITY));
+    }
+}
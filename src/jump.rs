@@ -0,0 +1,81 @@
+use std::cmp::Ordering;
+
+/// Internal jump search algorithm.
+///
+/// `f` compares a probed element against the target, as in `slice::binary_search_by`, and the
+/// result follows the same convention: `Ok` with a matching index, or `Err` with the insertion point.
+pub(crate) fn search_by<T, F>(collection: &[T], jump_size: usize, mut f: F) -> Result<usize, usize>
+where F: FnMut(&T) -> Ordering
+{
+    let jump_size = jump_size.max(1);
+    let mut i = jump_size;
+    while i < collection.len() {
+        match f(&collection[i]) {
+            Ordering::Equal => return Ok(i),
+            Ordering::Greater => return linear_search_by(collection, i - jump_size, i, f),
+            Ordering::Less => i += jump_size,
+        }
+    }
+    linear_search_by(collection, i - jump_size, collection.len(), f)
+}
+
+/// Jump search with `levels` levels of jumps before the final linear scan.
+///
+/// Each level jumps through the block the previous level found, in steps of
+/// `block^(k/(k+1))` with `k` levels remaining, so a single level is classic `sqrt(n)` jump
+/// search and `k` levels make O(k * n^(1/(k+1))) comparisons.
+pub(crate) fn search_levels_by<T, F>(collection: &[T], levels: usize, mut f: F) -> Result<usize, usize>
+where F: FnMut(&T) -> Ordering
+{
+    let (mut left, mut right) = (0, collection.len());
+    for remaining in (1..=levels).rev() {
+        let exponent = remaining as f64 / (remaining + 1) as f64;
+        let step = ((right - left) as f64).powf(exponent).ceil().max(1.0) as usize;
+        // Probe the last element of each block, so a miss narrows the range to one block.
+        let mut i = left + step - 1;
+        while i < right {
+            match f(&collection[i]) {
+                Ordering::Equal => return Ok(i),
+                Ordering::Greater => {
+                    right = i;
+                    break;
+                }
+                Ordering::Less => {
+                    left = i + 1;
+                    i += step;
+                }
+            }
+        }
+    }
+    linear_search_by(collection, left, right, f)
+}
+
+/// Helper function for jump search that linearly searches through an interval.
+///
+/// Returns `Err(right)` if every element in the interval orders before the target.
+pub(crate) fn linear_search_by<T, F>(collection: &[T], left: usize, right: usize, mut f: F) -> Result<usize, usize>
+where F: FnMut(&T) -> Ordering
+{
+    for (idx, v) in collection[left..right].iter().enumerate() {
+        match f(v) {
+            Ordering::Less => {}
+            Ordering::Equal => return Ok(left + idx),
+            Ordering::Greater => return Err(left + idx),
+        }
+    }
+    Err(right)
+}
+
+/// Jump search for the first element that does not satisfy `pred`, as in `slice::partition_point`.
+pub(crate) fn partition_point<T, P>(collection: &[T], jump_size: usize, mut pred: P) -> usize
+where P: FnMut(&T) -> bool
+{
+    let jump_size = jump_size.max(1);
+    let mut i = jump_size;
+    while i < collection.len() && pred(&collection[i]) {
+        i += jump_size;
+    }
+    let right = i.min(collection.len());
+    let left = i - jump_size;
+    collection[left..right].iter().position(|v| !pred(v)).map_or(right, |idx| left + idx)
+}
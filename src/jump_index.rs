@@ -0,0 +1,100 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+
+use crate::strategy::jump_size;
+use crate::{jump, HardwareProfile, Strategy};
+
+/// A read-only jump search index that keeps a copy of the fence keys in one dense array.
+///
+/// Jump search probes every `block_size`-th element, so each lookup touches a different cache
+/// line per probe, scattered across the whole collection. This index copies the last key of each
+/// block into a contiguous array once, so a lookup searches that array, which is small enough to
+/// stay cache-resident across lookups, and then scans the single block that can hold the target.
+///
+/// The index borrows the sorted collection and copies only the fence keys. Lookups return indices into it.
+#[derive(Debug, Clone)]
+pub struct JumpIndex<'a, T> {
+    keys: &'a [T],
+    /// The last key of each full block.
+    fences: Vec<T>,
+    block_size: usize,
+    /// How `fences` is searched, chosen once for its length.
+    strategy: Strategy,
+}
+
+impl<'a, T> JumpIndex<'a, T>
+where T: Clone
+{
+    /// Builds an index from a sorted collection, with `sqrt(n)` elements per block as in jump search.
+    pub fn new(sorted: &'a [T]) -> Self {
+        JumpIndex::with_block_size(sorted, jump_size(sorted.len()))
+    }
+
+    /// Builds an index from a sorted collection with the given number of elements per block.
+    pub fn with_block_size(sorted: &'a [T], block_size: usize) -> Self {
+        let block_size = block_size.max(1);
+        let fences: Vec<T> = sorted.chunks_exact(block_size).map(|block| block[block_size - 1].clone()).collect();
+        let strategy = Strategy::select::<T>(&HardwareProfile::current_or_conservative(), fences.len());
+        JumpIndex { keys: sorted, fences, block_size, strategy }
+    }
+}
+
+impl<T> JumpIndex<'_, T> {
+    /// Returns the number of indexed elements.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns `true` if the index holds no elements.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Returns the number of elements per block.
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    /// Looks up an element, returning its index in the sorted collection.
+    pub fn find<Q>(&self, item: &Q) -> Option<usize>
+    where T: Borrow<Q>, Q: ?Sized + Ord
+    {
+        self.search(item).ok()
+    }
+
+    /// Like [`JumpIndex::find`], but returns the insertion point on a miss, as in `slice::binary_search`.
+    pub fn search<Q>(&self, item: &Q) -> Result<usize, usize>
+    where T: Borrow<Q>, Q: ?Sized + Ord
+    {
+        self.search_by(|probe| probe.borrow().cmp(item))
+    }
+
+    /// Like [`JumpIndex::search`], but with a comparator that orders a probed element relative to the target.
+    pub fn search_by<F>(&self, mut f: F) -> Result<usize, usize>
+    where F: FnMut(&T) -> Ordering
+    {
+        let block = self.strategy.partition_point(&self.fences, |fence| f(fence) == Ordering::Less);
+        let left = block * self.block_size;
+        let right = (left + self.block_size).min(self.keys.len());
+        jump::linear_search_by(self.keys, left, right, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::JumpIndex;
+    use crate::testing::{assert_search_matches, sorted_fixtures};
+
+    #[test]
+    fn test_matches_sorted_search() {
+        for vec in sorted_fixtures() {
+            for index in [JumpIndex::new(&vec), JumpIndex::with_block_size(&vec, 7)].iter() {
+                assert_eq!(vec.len(), index.len());
+                assert_search_matches(&vec, |x| index.search(&x));
+            }
+        }
+
+        let strings: Vec<String> = ["ant", "bee", "cat", "dog"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(Some(2), JumpIndex::new(&strings).find("cat"));
+    }
+}
@@ -0,0 +1,258 @@
+use std::cmp::Ordering;
+
+use crate::interpolation::{compare, Interpolate};
+use crate::{HardwareProfile, Strategy};
+
+/// Average number of keys per second-stage model.
+const KEYS_PER_LEAF: usize = 128;
+
+/// A read-only learned index (a two-stage recursive model index) over sorted numeric keys.
+///
+/// A root linear model routes each key to one of many leaf linear models, each fitted by least
+/// squares to the keys routed to it and recording how far its predictions were off. A lookup
+/// evaluates two models and then searches only the window `prediction - max_error ..=
+/// prediction + max_error`. On smooth key sets such as sequential IDs or timestamps, that window
+/// is a few cache lines wide.
+///
+/// Once the models are fitted, the index decides whether lookups use them or, when the model is too
+/// inaccurate to pay off, search the keys directly with the strategy `find_numeric` would pick. Both choices, and the strategy for the error windows, are made
+/// once at build time.
+///
+/// Keys that were not indexed can fall outside the recorded error bounds; those lookups notice
+/// and gallop outwards from the prediction instead.
+#[derive(Debug, Clone)]
+pub struct LearnedIndex<'a, T> {
+    keys: &'a [T],
+    root: LinearModel,
+    leaves: Vec<Leaf>,
+    lookup: Lookup,
+    window_strategy: Strategy,
+}
+
+/// How a [`LearnedIndex`] answers lookups.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Lookup {
+    /// Evaluate the models and search the predicted window.
+    Model,
+    /// Search all the keys with the given strategy.
+    Keys(Strategy),
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct LinearModel {
+    slope: f64,
+    intercept: f64,
+}
+
+impl LinearModel {
+    /// Fits `y = slope * x + intercept` by least squares.
+    fn fit<I>(points: I) -> LinearModel
+    where I: Iterator<Item = (f64, f64)> + Clone
+    {
+        // Infinite positions (e.g. float infinities) would make every sum NaN; they are bounded by
+        // the lookups' edge checks instead.
+        let points = points.filter(|(x, _)| x.is_finite());
+        let n = points.clone().count() as f64;
+        if n == 0.0 {
+            return LinearModel::default();
+        }
+        let (sum_x, sum_y) = points.clone().fold((0.0, 0.0), |(sx, sy), (x, y)| (sx + x, sy + y));
+        let (mean_x, mean_y) = (sum_x / n, sum_y / n);
+        let (cov, var) = points.fold((0.0, 0.0), |(c, v), (x, y)| (c + (x - mean_x) * (y - mean_y), v + (x - mean_x) * (x - mean_x)));
+        let slope = if var > 0.0 { cov / var } else { 0.0 };
+        LinearModel { slope, intercept: mean_y - slope * mean_x }
+    }
+
+    fn predict(&self, x: f64) -> f64 {
+        self.slope * x + self.intercept
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Leaf {
+    model: LinearModel,
+    /// How far below and above its prediction an indexed key's position was.
+    error_below: usize,
+    error_above: usize,
+}
+
+impl<'a, T> LearnedIndex<'a, T>
+where T: Interpolate + PartialOrd
+{
+    /// Builds an index over sorted keys, with one leaf model per 128 keys.
+    pub fn new(sorted: &'a [T]) -> Self {
+        LearnedIndex::with_leaves(sorted, sorted.len() / KEYS_PER_LEAF)
+    }
+
+    /// Builds an index over sorted keys with the given number of leaf models.
+    pub fn with_leaves(sorted: &'a [T], leaves: usize) -> Self {
+        let leaf_count = leaves.max(1);
+        let positions: Vec<f64> = sorted.iter().map(Interpolate::position).collect();
+        let scale = leaf_count as f64 / sorted.len().max(1) as f64;
+        let root = LinearModel::fit(positions.iter().enumerate().map(|(rank, x)| (*x, rank as f64 * scale)));
+
+        let mut index = LearnedIndex {
+            keys: sorted,
+            root,
+            leaves: vec![Leaf::default(); leaf_count],
+            lookup: Lookup::Model,
+            window_strategy: Strategy::Linear,
+        };
+        let routes: Vec<usize> = positions.iter().map(|x| index.route(*x)).collect();
+        let mut start = 0;
+        while start < sorted.len() {
+            // The root model is monotone, so the keys routed to each leaf form a contiguous run.
+            let leaf = routes[start];
+            let end = start + routes[start..].iter().take_while(|r| **r == leaf).count();
+            let points = (start..end).map(|rank| (positions[rank], rank as f64));
+            let model = LinearModel::fit(points.clone());
+            let (mut below, mut above) = (0.0f64, 0.0f64);
+            for (x, rank) in points {
+                let error = rank - model.predict(x);
+                // A NaN error would poison the maximum; such keys fall back to galloping at lookup time.
+                if error.is_finite() {
+                    below = below.max(-error);
+                    above = above.max(error);
+                }
+            }
+            index.leaves[leaf] = Leaf { model, error_below: below.ceil() as usize, error_above: above.ceil() as usize };
+            start = end;
+        }
+        let profile = HardwareProfile::current_or_conservative();
+        index.lookup = lookup(&profile, sorted, index.max_error());
+        index.window_strategy = Strategy::select::<T>(&profile, index.max_error() + 1);
+        index
+    }
+
+    /// Returns the number of indexed keys.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns `true` if the index holds no keys.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Returns the indexed keys in sorted order.
+    pub fn keys(&self) -> &'a [T] {
+        self.keys
+    }
+
+    /// Returns `true` if lookups go through the models, or `false` if the models were too inaccurate
+    /// to beat searching the keys directly.
+    pub fn uses_model(&self) -> bool {
+        self.lookup == Lookup::Model
+    }
+
+    /// Returns the largest error bound of any leaf model, i.e. the widest window a lookup of an indexed key can search.
+    pub fn max_error(&self) -> usize {
+        self.leaves.iter().map(|leaf| leaf.error_below + leaf.error_above).max().unwrap_or(0)
+    }
+
+    /// Looks up a key, returning its index in the sorted keys.
+    pub fn find(&self, key: &T) -> Option<usize> {
+        self.search(key).ok()
+    }
+
+    /// Like [`LearnedIndex::find`], but returns the insertion point on a miss, as in `slice::binary_search`.
+    pub fn search(&self, key: &T) -> Result<usize, usize> {
+        match self.lookup {
+            Lookup::Model => self.search_model(key),
+            Lookup::Keys(strategy) => strategy.search_numeric(self.keys, key),
+        }
+    }
+
+    /// Searches the window the models predict for `key`.
+    fn search_model(&self, key: &T) -> Result<usize, usize> {
+        let (prediction, start, end) = self.window(key);
+        // The window is usable only if everything before it orders before `key` and everything
+        // after it orders after `key`; incomparable keys (e.g. `NaN`) count as before, as in `compare`.
+        let outside = (start > 0 && compare(&self.keys[start - 1], key) != Ordering::Less)
+            || (end < self.keys.len() && compare(&self.keys[end], key) != Ordering::Greater);
+        let strategy = if outside { Strategy::Exponential { hint: prediction } } else { self.window_strategy };
+        let (start, end) = if outside { (0, self.keys.len()) } else { (start, end) };
+        let offset = |idx: usize| start + idx;
+        strategy
+            .search_by(&self.keys[start..end], |probe| compare(probe, key))
+            .map(offset)
+            .map_err(offset)
+    }
+
+    /// Returns the predicted position of `key` and the window `start..end` its leaf's error bounds allow.
+    fn window(&self, key: &T) -> (usize, usize, usize) {
+        let x = key.position();
+        let leaf = &self.leaves[self.route(x)];
+        let prediction = clamp(leaf.model.predict(x), self.keys.len());
+        let start = prediction.saturating_sub(leaf.error_below);
+        let end = (prediction + leaf.error_above + 1).min(self.keys.len());
+        (prediction, start, end.max(start))
+    }
+
+    /// Returns the leaf the root model routes position `x` to.
+    fn route(&self, x: f64) -> usize {
+        clamp(self.root.predict(x), self.leaves.len() - 1)
+    }
+}
+
+/// Decides how an index whose models are off by at most `max_error` positions answers lookups.
+///
+/// The models are used when evaluating them and searching their error window (about
+/// `2 + log2(max_error)` probes) beats searching the whole collection (about `log2(len)`).
+fn lookup<T>(profile: &HardwareProfile, collection: &[T], max_error: usize) -> Lookup
+where T: Interpolate
+{
+    match Strategy::select_numeric::<T, T>(profile, collection) {
+        Strategy::Linear => Lookup::Keys(Strategy::Linear),
+        _ if max_error.saturating_add(1).saturating_mul(4) < collection.len() => Lookup::Model,
+        strategy => Lookup::Keys(strategy),
+    }
+}
+
+/// Rounds a prediction to an index in `0..=max`, mapping `NaN` to 0.
+fn clamp(prediction: f64, max: usize) -> usize {
+    if prediction.is_nan() || prediction <= 0.0 {
+        0
+    } else {
+        (prediction as usize).min(max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LearnedIndex;
+
+    #[test]
+    fn test_matches_sorted_search() {
+        let linear: Vec<u64> = (0..10_000).map(|x| x * 1_000 + 17).collect();
+        let curved: Vec<u64> = (0..10_000u64).map(|x| x * x / 3 + x).collect();
+        let clustered: Vec<u64> = (0..5_000).chain(1 << 40..(1 << 40) + 5_000).collect();
+        for keys in [linear, curved, clustered].iter() {
+            let index = LearnedIndex::new(keys);
+            assert_eq!(keys.len(), index.len());
+            assert!(index.uses_model());
+            for (rank, key) in keys.iter().enumerate().step_by(7) {
+                assert_eq!(Ok(rank), index.search(key));
+                let missing = key + 1;
+                if keys.binary_search(&missing).is_err() {
+                    assert_eq!(keys.binary_search(&missing), index.search(&missing));
+                }
+            }
+            assert_eq!(keys.binary_search(&0), index.search(&0));
+            assert_eq!(Err(keys.len()), index.search(&u64::MAX));
+        }
+        let floats = [f64::NEG_INFINITY, -1.0, 0.0, 2.0, f64::INFINITY];
+        let index = LearnedIndex::new(&floats);
+        for (rank, key) in floats.iter().enumerate() {
+            assert_eq!(Ok(rank), index.search_model(key));
+        }
+        assert_eq!(Err(3), index.search_model(&1.0));
+        let linear: Vec<u64> = (0..10_000).collect();
+        assert!(LearnedIndex::new(&linear).max_error() <= 2);
+        let scattered: Vec<u64> = (0..1_000u64).map(|x| x.pow(6)).collect();
+        let index = LearnedIndex::with_leaves(&scattered, 1);
+        assert!(!index.uses_model());
+        assert_eq!(Ok(500), index.search(&500u64.pow(6)));
+        assert_eq!(Err(0), LearnedIndex::<u64>::new(&[]).search(&3));
+    }
+}
@@ -1,70 +1,405 @@
-use raw_cpuid::{CacheType, CpuId};
+mod aligned;
+mod batch;
+mod bounds;
+mod branchless;
+mod bucket;
+mod error;
+mod executor;
+mod exponential;
+mod ext;
+mod eytzinger;
+mod fibonacci;
+mod hardware;
+mod interpolation;
+mod jump;
+mod jump_index;
+mod learned;
+mod pgm;
+mod prefetch;
+mod range;
+mod simd;
+mod static_btree;
+mod strategy;
+#[cfg(test)]
+mod testing;
+mod van_emde_boas;
+
+pub use batch::{find_many, search_many};
+pub use bucket::{digitize, searchsorted, Side};
+pub use bounds::{count, count_by, equal_range, equal_range_by, lower_bound, lower_bound_by, upper_bound, upper_bound_by};
+pub use error::SearchError;
+pub use executor::{BatchExecutor, BatchStrategy};
+pub use ext::SmartSearchExt;
+pub use eytzinger::EytzingerIndex;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+pub use hardware::CpuidProvider;
+pub use hardware::{CacheInfoProvider, CacheLevel, ConservativeProvider, HardwareProfile, SysfsProvider};
+pub use interpolation::Interpolate;
+pub use jump_index::JumpIndex;
+pub use learned::LearnedIndex;
+pub use pgm::PgmIndex;
+pub use range::{range, RangeMatch};
+pub use simd::SimdKey;
+pub use static_btree::StaticBTreeIndex;
+pub use strategy::{Hint, Strategy};
+pub use van_emde_boas::VanEmdeBoasIndex;
+
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+
+use strategy::{jump_levels, jump_size};
 
 /// A cache-aware search function for sorted collections.
-pub fn find<T>(collection: &Vec<T>, item: &T) -> Option<usize>
-where T: Ord
-{
-    let jump_size = get_optimal_jump_size(collection);
-    let cache_line_size = CpuId::new()
-        .get_cache_parameters()?
-        .filter(|c| c.cache_type() == CacheType::Data)
-        .map(|c| c.coherency_line_size())
-        .min(); // TODO-Q: Can there be multiple data caches?
-    if cache_line_size.is_some() && cache_line_size.unwrap() <= jump_size {
-        return match collection.binary_search(item) {
-            Ok(idx) => Some(idx),
-            Err(_) => None,
-        }
-    } else {
-        return find_jump_with_size(collection, item, jump_size);
-    }
+///
+/// Like `BTreeMap::get`, the item may be any borrowed form of the element type, so a `Vec<String>`
+/// can be searched with a `&str`.
+///
+/// If the cache hierarchy cannot be detected, this tunes itself against [`HardwareProfile::conservative`].
+///
+/// Elements are compared one at a time through `Ord`, so this is not SIMD-accelerated; for
+/// primitive keys, [`find_simd`] runs the same dispatcher with vectorised linear scans.
+pub fn find<T, Q>(collection: &[T], item: &Q) -> Option<usize>
+where T: Borrow<Q>, Q: ?Sized + Ord
+{
+    search(collection, item).ok()
+}
+
+/// Like [`find`], but with a comparator that orders a probed element relative to the target.
+pub fn find_by<T, F>(collection: &[T], f: F) -> Option<usize>
+where F: FnMut(&T) -> Ordering
+{
+    search_by(collection, f).ok()
+}
+
+/// Like [`find`], but compares the key extracted from each element by `f`.
+pub fn find_by_key<T, K, F>(collection: &[T], key: &K, f: F) -> Option<usize>
+where K: Ord, F: FnMut(&T) -> K
+{
+    search_by_key(collection, key, f).ok()
+}
+
+/// A cache-aware search function for sorted collections that reports why cache detection failed.
+pub fn try_find<T, Q>(collection: &[T], item: &Q) -> Result<Option<usize>, SearchError>
+where T: Borrow<Q>, Q: ?Sized + Ord
+{
+    try_find_by(collection, |probe| probe.borrow().cmp(item))
+}
+
+/// Like [`try_find`], but with a comparator that orders a probed element relative to the target.
+pub fn try_find_by<T, F>(collection: &[T], f: F) -> Result<Option<usize>, SearchError>
+where F: FnMut(&T) -> Ordering
+{
+    try_find_by_with_detected(collection, HardwareProfile::current(), f)
+}
+
+/// Internal version of [`try_find_by`], given the outcome of cache detection.
+fn try_find_by_with_detected<T, F>(collection: &[T], detected: Result<HardwareProfile, SearchError>, f: F) -> Result<Option<usize>, SearchError>
+where F: FnMut(&T) -> Ordering
+{
+    Ok(search_by_with_profile(collection, &detected?, f).ok())
+}
+
+/// Like [`try_find`], but compares the key extracted from each element by `f`.
+pub fn try_find_by_key<T, K, F>(collection: &[T], key: &K, mut f: F) -> Result<Option<usize>, SearchError>
+where K: Ord, F: FnMut(&T) -> K
+{
+    try_find_by(collection, |probe| f(probe).cmp(key))
+}
+
+/// Like [`find`], but with `slice::binary_search` semantics: `Ok` with a matching index, or `Err`
+/// with the index where the item could be inserted to keep the collection sorted.
+pub fn search<T, Q>(collection: &[T], item: &Q) -> Result<usize, usize>
+where T: Borrow<Q>, Q: ?Sized + Ord
+{
+    search_by(collection, |probe| probe.borrow().cmp(item))
+}
+
+/// Like [`search`], but with a comparator that orders a probed element relative to the target.
+pub fn search_by<T, F>(collection: &[T], f: F) -> Result<usize, usize>
+where F: FnMut(&T) -> Ordering
+{
+    search_by_with_detected(collection, HardwareProfile::current(), f)
+}
+
+/// Internal version of [`search_by`], given the outcome of cache detection; a failure falls back to [`HardwareProfile::conservative`].
+fn search_by_with_detected<T, F>(collection: &[T], detected: Result<HardwareProfile, SearchError>, f: F) -> Result<usize, usize>
+where F: FnMut(&T) -> Ordering
+{
+    search_by_with_profile(collection, &detected.unwrap_or_else(|_| HardwareProfile::conservative()), f)
+}
+
+/// Like [`search`], but compares the key extracted from each element by `f`.
+pub fn search_by_key<T, K, F>(collection: &[T], key: &K, mut f: F) -> Result<usize, usize>
+where K: Ord, F: FnMut(&T) -> K
+{
+    search_by(collection, |probe| f(probe).cmp(key))
+}
+
+/// Internal cache-aware dispatcher, tuned against an explicit hardware profile.
+fn search_by_with_profile<T, F>(collection: &[T], profile: &HardwareProfile, f: F) -> Result<usize, usize>
+where F: FnMut(&T) -> Ordering
+{
+    Strategy::select::<T>(profile, collection.len()).search_by(collection, f)
+}
+
+/// Like [`search`], but lets the caller say where targets tend to be.
+///
+/// With [`Hint::Front`] or [`Hint::Near`] this uses exponential search from the hinted position,
+/// which needs `O(log d)` probes for a target `d` elements away.
+pub fn search_with_hint<T, Q>(collection: &[T], item: &Q, hint: Hint) -> Result<usize, usize>
+where T: Borrow<Q>, Q: ?Sized + Ord
+{
+    search_by_with_hint(collection, hint, |probe| probe.borrow().cmp(item))
+}
+
+/// Like [`search_with_hint`], but with a comparator that orders a probed element relative to the target.
+pub fn search_by_with_hint<T, F>(collection: &[T], hint: Hint, f: F) -> Result<usize, usize>
+where F: FnMut(&T) -> Ordering
+{
+    let profile = HardwareProfile::current_or_conservative();
+    Strategy::select_with_hint::<T>(&profile, collection.len(), hint).search_by(collection, f)
+}
+
+/// Like [`find`], but lets the caller say where targets tend to be (see [`search_with_hint`]).
+pub fn find_with_hint<T, Q>(collection: &[T], item: &Q, hint: Hint) -> Option<usize>
+where T: Borrow<Q>, Q: ?Sized + Ord
+{
+    search_with_hint(collection, item, hint).ok()
+}
+
+/// Find an element in a sorted collection using exponential search from the front.
+pub fn find_exponential<T, Q>(collection: &[T], item: &Q) -> Option<usize>
+where T: Borrow<Q>, Q: ?Sized + Ord
+{
+    search_exponential(collection, item).ok()
+}
+
+/// Like [`find_exponential`], but returns the insertion point on a miss (see [`search`]).
+pub fn search_exponential<T, Q>(collection: &[T], item: &Q) -> Result<usize, usize>
+where T: Borrow<Q>, Q: ?Sized + Ord
+{
+    search_exponential_by(collection, |probe| probe.borrow().cmp(item))
+}
+
+/// Like [`search_exponential`], but with a comparator that orders a probed element relative to the target.
+pub fn search_exponential_by<T, F>(collection: &[T], f: F) -> Result<usize, usize>
+where F: FnMut(&T) -> Ordering
+{
+    Strategy::Exponential { hint: 0 }.search_by(collection, f)
+}
+
+/// Find an element in a sorted collection using Fibonacci search.
+pub fn find_fibonacci<T, Q>(collection: &[T], item: &Q) -> Option<usize>
+where T: Borrow<Q>, Q: ?Sized + Ord
+{
+    search_fibonacci(collection, item).ok()
+}
+
+/// Like [`find_fibonacci`], but returns the insertion point on a miss (see [`search`]).
+pub fn search_fibonacci<T, Q>(collection: &[T], item: &Q) -> Result<usize, usize>
+where T: Borrow<Q>, Q: ?Sized + Ord
+{
+    search_fibonacci_by(collection, |probe| probe.borrow().cmp(item))
+}
+
+/// Like [`search_fibonacci`], but with a comparator that orders a probed element relative to the target.
+pub fn search_fibonacci_by<T, F>(collection: &[T], f: F) -> Result<usize, usize>
+where F: FnMut(&T) -> Ordering
+{
+    Strategy::Fibonacci.search_by(collection, f)
+}
+
+/// A cache-aware search function for sorted collections of numeric keys.
+///
+/// Like [`find`], but interpolation search is also considered, and chosen when the keys look
+/// roughly uniformly distributed. Keys that cannot be compared (e.g. `NaN`) are never found.
+pub fn find_numeric<T, Q>(collection: &[T], item: &Q) -> Option<usize>
+where T: Borrow<Q>, Q: ?Sized + Interpolate + PartialOrd
+{
+    search_numeric(collection, item).ok()
+}
+
+/// Like [`find_numeric`], but returns the insertion point on a miss (see [`search`]).
+pub fn search_numeric<T, Q>(collection: &[T], item: &Q) -> Result<usize, usize>
+where T: Borrow<Q>, Q: ?Sized + Interpolate + PartialOrd
+{
+    let profile = HardwareProfile::current_or_conservative();
+    Strategy::select_numeric(&profile, collection).search_numeric(collection, item)
+}
+
+/// Find an element in a sorted collection of numeric keys using interpolation search.
+///
+/// Falls back to binary search for the remaining window if the keys turn out to be skewed.
+pub fn find_interpolation<T, Q>(collection: &[T], item: &Q) -> Option<usize>
+where T: Borrow<Q>, Q: ?Sized + Interpolate + PartialOrd
+{
+    interpolation::search(collection, item).ok()
+}
+
+/// Find an element in a sorted collection using branchless binary search with prefetching.
+pub fn find_branchless<T, Q>(collection: &[T], item: &Q) -> Option<usize>
+where T: Borrow<Q>, Q: ?Sized + Ord
+{
+    search_branchless(collection, item).ok()
+}
+
+/// Like [`find_branchless`], but returns the insertion point on a miss (see [`search`]).
+pub fn search_branchless<T, Q>(collection: &[T], item: &Q) -> Result<usize, usize>
+where T: Borrow<Q>, Q: ?Sized + Ord
+{
+    search_branchless_by(collection, |probe| probe.borrow().cmp(item))
+}
+
+/// Like [`search_branchless`], but with a comparator that orders a probed element relative to the target.
+pub fn search_branchless_by<T, F>(collection: &[T], f: F) -> Result<usize, usize>
+where F: FnMut(&T) -> Ordering
+{
+    Strategy::Branchless { prefetch: true }.search_by(collection, f)
 }
 
 /// Find an element in a sorted collection using jump search.
-pub fn find_jump<T>(collection: &Vec<T>, item: &T) -> Option<usize>
-where T: Ord
+pub fn find_jump<T, Q>(collection: &[T], item: &Q) -> Option<usize>
+where T: Borrow<Q>, Q: ?Sized + Ord
 {
-    find_jump_with_size(collection, item, get_optimal_jump_size(collection))
+    search_jump(collection, item).ok()
 }
 
-/// Internal jump search algorithm.
-fn find_jump_with_size<T>(collection: &Vec<T>, item: &T, jump_size: usize) -> Option<usize>
-where T: Ord
+/// Like [`find`], but for primitive keys, so the dispatcher's linear scans (a whole small collection,
+/// or the final block of a jump search) compare many elements at once with SIMD.
+pub fn find_simd<T>(collection: &[T], item: &T) -> Option<usize>
+where T: SimdKey
 {
-    let mut i = jump_size;
-    while i < collection.len() {
-        if collection[i] == *item {
-            return Some(i);
-        }
-        if collection[i] > *item {
-            if let Some(idx) = linear_search(collection, item, i - jump_size, i) {
-                return Some(idx);
-            }
-        }
-        i += jump_size;
-    }
-    linear_search(collection, item, i - jump_size, collection.len())
+    search_simd(collection, item).ok()
 }
 
-/// Helper function for jump search that linearly searches through an interval.
-fn linear_search<T>(collection: &Vec<T>, item: &T, left: usize, right: usize) -> Option<usize>
-where T: Ord
+/// Like [`find_simd`], but returns the insertion point on a miss (see [`search`]).
+pub fn search_simd<T>(collection: &[T], item: &T) -> Result<usize, usize>
+where T: SimdKey
 {
-    match collection[left..right].iter().position(|v| v == item) {
-        Some(idx) => Some(left + idx),
-        None => None,
-    }
+    let profile = HardwareProfile::current_or_conservative();
+    Strategy::select::<T>(&profile, collection.len()).search_simd(collection, item)
+}
+
+/// Like [`find_jump`], but for primitive keys, whose final block is scanned with SIMD comparisons.
+pub fn find_jump_simd<T>(collection: &[T], item: &T) -> Option<usize>
+where T: SimdKey
+{
+    simd::search_jump(collection, jump_size(collection.len()), *item).ok()
+}
+
+/// Find a primitive key in a small sorted collection by comparing every element with SIMD.
+///
+/// This is faster than any of the searches for collections spanning only a few cache lines.
+pub fn find_scan<T>(collection: &[T], item: &T) -> Option<usize>
+where T: SimdKey
+{
+    simd::scan(collection, *item).ok()
+}
+
+/// Like [`find_jump`], but with a comparator that orders a probed element relative to the target.
+pub fn find_jump_by<T, F>(collection: &[T], f: F) -> Option<usize>
+where F: FnMut(&T) -> Ordering
+{
+    search_jump_by(collection, f).ok()
+}
+
+/// Like [`find_jump`], but compares the key extracted from each element by `f`.
+pub fn find_jump_by_key<T, K, F>(collection: &[T], key: &K, f: F) -> Option<usize>
+where K: Ord, F: FnMut(&T) -> K
+{
+    search_jump_by_key(collection, key, f).ok()
 }
 
-/// Returns the square root of the collection size, which is mathematically proven to be the optimal jump size for jump search.
-fn get_optimal_jump_size<T>(collection: &Vec<T>) -> usize {
-    ((collection.len() as f64).sqrt()) as usize
+/// Like [`find_jump`], but the block found by each jump is itself jump searched, so large
+/// collections need O(k * n^(1/(k+1))) comparisons rather than O(sqrt n).
+///
+/// The number of levels `k` is picked from the cache level the collection fits in.
+pub fn find_jump_multilevel<T, Q>(collection: &[T], item: &Q) -> Option<usize>
+where T: Borrow<Q>, Q: ?Sized + Ord
+{
+    search_jump_multilevel(collection, item).ok()
+}
+
+/// Like [`find_jump_multilevel`], but returns the insertion point on a miss (see [`search`]).
+pub fn search_jump_multilevel<T, Q>(collection: &[T], item: &Q) -> Result<usize, usize>
+where T: Borrow<Q>, Q: ?Sized + Ord
+{
+    search_jump_multilevel_by(collection, |probe| probe.borrow().cmp(item))
+}
+
+/// Like [`search_jump_multilevel`], but with a comparator that orders a probed element relative to the target.
+pub fn search_jump_multilevel_by<T, F>(collection: &[T], f: F) -> Result<usize, usize>
+where F: FnMut(&T) -> Ordering
+{
+    let levels = jump_levels::<T>(&HardwareProfile::current_or_conservative(), collection.len());
+    jump::search_levels_by(collection, levels, f)
+}
+
+/// Like [`find_jump`], but returns the insertion point on a miss (see [`search`]).
+pub fn search_jump<T, Q>(collection: &[T], item: &Q) -> Result<usize, usize>
+where T: Borrow<Q>, Q: ?Sized + Ord
+{
+    search_jump_by(collection, |probe| probe.borrow().cmp(item))
+}
+
+/// Like [`search_jump`], but with a comparator that orders a probed element relative to the target.
+pub fn search_jump_by<T, F>(collection: &[T], f: F) -> Result<usize, usize>
+where F: FnMut(&T) -> Ordering
+{
+    jump::search_by(collection, jump_size(collection.len()), f)
+}
+
+/// Like [`search_jump`], but compares the key extracted from each element by `f`.
+pub fn search_jump_by_key<T, K, F>(collection: &[T], key: &K, mut f: F) -> Result<usize, usize>
+where K: Ord, F: FnMut(&T) -> K
+{
+    search_jump_by(collection, |probe| f(probe).cmp(key))
 }
 
 mod tests {
     // TODO-Q: Why do we have to do this?
     #[allow(unused_imports)]
-    use super::find_jump;
+    use super::{find, find_by, find_by_key, find_exponential, find_interpolation, find_jump, find_jump_by_key, find_jump_multilevel, find_numeric, find_with_hint, search, search_by_with_detected, search_by_with_profile, search_jump, try_find_by_with_detected};
+    #[allow(unused_imports)]
+    use crate::{CacheLevel, HardwareProfile, Hint, SearchError};
+
+    #[test]
+    fn test_search_fibonacci() {
+        let vec: Vec<u32> = (0..100).map(|x| x * 2).collect();
+        assert_eq!(Ok(21), super::search_fibonacci(&vec, &42));
+        assert_eq!(Err(22), super::search_fibonacci(&vec, &43));
+        assert_eq!(Err(100), super::search_fibonacci_by(&vec, |v| v.cmp(&500)));
+    }
+
+    #[test]
+    fn test_search_branchless() {
+        let vec: Vec<u32> = (0..100).map(|x| x * 2).collect();
+        assert_eq!(Ok(21), super::search_branchless(&vec, &42));
+        assert_eq!(Err(22), super::search_branchless(&vec, &43));
+        assert_eq!(Err(100), super::search_branchless_by(&vec, |v| v.cmp(&500)));
+    }
+
+    #[test]
+    fn test_search_exponential() {
+        let vec: Vec<u32> = (0..100).map(|x| x * 2).collect();
+        assert_eq!(Ok(21), super::search_exponential(&vec, &42));
+        assert_eq!(Err(22), super::search_exponential(&vec, &43));
+        assert_eq!(Err(100), super::search_exponential_by(&vec, |v| v.cmp(&500)));
+    }
+
+    #[test]
+    fn test_detection_failure() {
+        let vec: Vec<u32> = (0..1000).map(|x| x * 2).collect();
+        for error in [SearchError::CacheParametersUnavailable, SearchError::NoDataCache].iter() {
+            assert_eq!(Err(*error), try_find_by_with_detected(&vec, Err(*error), |v| v.cmp(&500)));
+            for x in 0..2002 {
+                assert_eq!(vec.binary_search(&x), search_by_with_detected(&vec, Err(*error), |v| v.cmp(&x)));
+            }
+        }
+        let detected = Ok(HardwareProfile::conservative());
+        assert_eq!(Ok(Some(250)), try_find_by_with_detected(&vec, detected, |v| v.cmp(&500)));
+        assert_eq!(Ok(None), try_find_by_with_detected(&vec, detected, |v| v.cmp(&501)));
+    }
 
     #[test]
     fn test_find_jump() {
@@ -80,4 +415,91 @@ mod tests {
         assert_eq!(Some(28), find_jump(&vec, &317811));
         assert_eq!(None, find_jump(&vec, &500));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_search_jump_levels() {
+        let vec: Vec<u32> = (0..5_000).map(|x| x * 3).collect();
+        for levels in 0..=4 {
+            for x in 0..15_010 {
+                assert_eq!(vec.binary_search(&x), crate::jump::search_levels_by(&vec, levels, |v| v.cmp(&x)));
+            }
+        }
+        assert_eq!(Some(1000), find_jump_multilevel(&vec, &3000));
+        assert_eq!(Err(0), crate::jump::search_levels_by(&[] as &[u32], 3, |v| v.cmp(&1)));
+    }
+
+    #[test]
+    fn test_find() {
+        let vec = vec![0, 1, 1, 2, 3, 5, 8, 13, 21, 34, 55, 89, 144, 233, 377, 610, 987, 1597, 2584, 4181, 6765, 10946, 17711, 28657, 46368, 75025, 121393, 196418, 317811];
+        assert_eq!(Some(0), find(&vec, &0));
+        assert_eq!(Some(4), find(&vec, &3));
+        assert_eq!(Some(28), find(&vec, &317811));
+        assert_eq!(None, find(&vec, &500));
+    }
+
+    #[test]
+    fn test_find_borrowed() {
+        let strings: Vec<String> = ["apple", "banana", "cherry", "date"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(Some(2), find(&strings, "cherry"));
+        assert_eq!(Some(1), find_jump(&strings, "banana"));
+        assert_eq!(None, find(&strings, "fig"));
+
+        let bytes: Vec<Vec<u8>> = vec![b"ab".to_vec(), b"cd".to_vec(), b"ef".to_vec()];
+        assert_eq!(Some(1), find(&bytes, &b"cd"[..]));
+    }
+
+    #[test]
+    fn test_find_by_key() {
+        struct Event { id: u32, name: &'static str }
+        let events: Vec<Event> = (0..100).map(|id| Event { id: id * 3, name: "tick" }).collect();
+        assert_eq!(Some(10), find_by_key(&events, &30, |e| e.id));
+        assert_eq!(Some(99), find_jump_by_key(&events, &297, |e| e.id));
+        assert_eq!(None, find_by_key(&events, &31, |e| e.id));
+        assert_eq!(Some(5), find_by(&events, |e| e.id.cmp(&15).then(e.name.cmp("tick"))));
+
+        let descending: Vec<i32> = (0..50).rev().collect();
+        assert_eq!(Some(40), find_by(&descending, |probe| 9.cmp(probe)));
+    }
+
+    #[test]
+    fn test_search_insertion_point() {
+        let vec: Vec<u32> = (0..1000).map(|x| x * 2).collect();
+        let profiles = [
+            HardwareProfile::conservative(),
+            HardwareProfile { l1: Some(CacheLevel { size: 1 << 20, line_size: 4096, associativity: 8 }), l2: None, l3: None },
+            HardwareProfile { l1: Some(CacheLevel { size: 1 << 20, line_size: 128, associativity: 8 }), l2: None, l3: None },
+        ];
+        for x in 0..2002 {
+            let expected = vec.binary_search(&x);
+            assert_eq!(expected, search(&vec, &x));
+            assert_eq!(expected, search_jump(&vec, &x));
+            for profile in profiles.iter() {
+                assert_eq!(expected, search_by_with_profile(&vec, profile, |probe| probe.cmp(&x)));
+            }
+        }
+        assert_eq!(Err(0), search::<u32, u32>(&[], &3));
+        assert_eq!(Err(0), search_jump::<u32, u32>(&[], &3));
+    }
+
+    #[test]
+    fn test_find_with_hint() {
+        let vec: Vec<u64> = (0..10_000).map(|x| x * 7).collect();
+        assert_eq!(Some(3), find_exponential(&vec, &21));
+        assert_eq!(None, find_exponential(&vec, &22));
+        assert_eq!(Some(9_999), find_with_hint(&vec, &69_993, Hint::Front));
+        assert_eq!(Some(5_001), find_with_hint(&vec, &35_007, Hint::Near(5_000)));
+        assert_eq!(Some(4_990), find_with_hint(&vec, &34_930, Hint::Near(5_000)));
+        assert_eq!(None, find_with_hint(&vec, &34_931, Hint::None));
+    }
+
+    #[test]
+    fn test_find_numeric() {
+        let ids: Vec<u64> = (0..5000).map(|x| x * 1_000_003).collect();
+        let times: Vec<f64> = (0..5000).map(|x| (x as f64) * 0.25).collect();
+        assert_eq!(Some(4321), find_numeric(&ids, &4_321_012_963));
+        assert_eq!(Some(4321), find_interpolation(&ids, &4_321_012_963));
+        assert_eq!(None, find_numeric(&ids, &4_321_012_964));
+        assert_eq!(Some(17), find_numeric(&times, &4.25));
+        assert_eq!(None, find_numeric(&times, &f64::NAN));
+    }
+}
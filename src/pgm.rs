@@ -0,0 +1,188 @@
+use std::cmp::Ordering;
+
+use crate::interpolation::{compare, Interpolate};
+use crate::simd::{self, SimdKey};
+use crate::{HardwareProfile, Strategy};
+
+/// A read-only piecewise geometric model (PGM) index over sorted numeric keys.
+///
+/// The keys are covered by line segments chosen so that, for every indexed key, the segment's
+/// prediction is within `epsilon` positions of the key's first occurrence. A lookup finds the
+/// segment in a compact array of segment start keys, evaluates one line, and scans a window of
+/// at most `2 * epsilon + 3` keys with the SIMD kernels, so its worst case is fixed at build time.
+///
+/// Segments are fitted in a single streaming pass with the shrinking-cone method: each segment
+/// keeps the range of slopes that still satisfies every key seen so far and is closed as soon
+/// as that range becomes empty. Distinct keys that map to the same [`Interpolate::position`]
+/// (e.g. `u64` keys above 2^53) cannot be told apart by the model; lookups verify the window
+/// and gallop from the prediction if it does not contain the answer.
+#[derive(Debug, Clone)]
+pub struct PgmIndex<'a, T> {
+    keys: &'a [T],
+    epsilon: usize,
+    /// The first key of each segment, in a contiguous array so the segment search stays cache-resident.
+    starts: Vec<T>,
+    segments: Vec<Segment>,
+    /// How `starts` is searched, chosen once for its length.
+    strategy: Strategy,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Segment {
+    position: f64,
+    rank: usize,
+    slope: f64,
+}
+
+impl<'a, T> PgmIndex<'a, T>
+where T: SimdKey + Interpolate
+{
+    /// Builds an index over sorted keys whose predictions are within `epsilon` of each key's position.
+    ///
+    /// An `epsilon` of `sorted.len()` or more allows any prediction, so it is clamped to that.
+    pub fn new(sorted: &'a [T], epsilon: usize) -> Self {
+        let epsilon = epsilon.min(sorted.len());
+        let mut index = PgmIndex { keys: sorted, epsilon, starts: Vec::new(), segments: Vec::new(), strategy: Strategy::Linear };
+        let eps = epsilon as f64;
+        let (mut low, mut high) = (0.0, f64::INFINITY);
+        let mut previous = None;
+        for (rank, key) in sorted.iter().enumerate() {
+            let position = key.position();
+            // Later occurrences of a key are found by the first one's lower bound.
+            if previous.replace(position) == Some(position) {
+                continue;
+            }
+            let segment = match index.segments.last_mut() {
+                Some(segment) => segment,
+                None => {
+                    index.push(*key, position, rank);
+                    continue;
+                }
+            };
+            let dx = position - segment.position;
+            let dy = (rank - segment.rank) as f64;
+            let (lo, hi) = (((dy - eps) / dx).max(low), ((dy + eps) / dx).min(high));
+            if lo <= hi && position.is_finite() {
+                low = lo;
+                high = hi;
+                segment.slope = if high.is_finite() { (low + high) / 2.0 } else { low };
+            } else {
+                index.push(*key, position, rank);
+                low = 0.0;
+                high = f64::INFINITY;
+            }
+        }
+        index.strategy = Strategy::select::<T>(&HardwareProfile::current_or_conservative(), index.starts.len());
+        index
+    }
+
+    fn push(&mut self, key: T, position: f64, rank: usize) {
+        self.starts.push(key);
+        self.segments.push(Segment { position, rank, slope: 0.0 });
+    }
+
+    /// Returns the number of indexed keys.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns `true` if the index holds no keys.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Returns the indexed keys in sorted order.
+    pub fn keys(&self) -> &'a [T] {
+        self.keys
+    }
+
+    /// Returns the error bound the index was built with.
+    pub fn epsilon(&self) -> usize {
+        self.epsilon
+    }
+
+    /// Returns the number of linear segments the keys were split into.
+    pub fn segments(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Looks up a key, returning the index of its first occurrence in the sorted keys.
+    pub fn find(&self, key: &T) -> Option<usize> {
+        self.search(key).ok()
+    }
+
+    /// Like [`PgmIndex::find`], but returns the insertion point on a miss, as in `slice::binary_search`.
+    pub fn search(&self, key: &T) -> Result<usize, usize> {
+        let key = *key;
+        let (prediction, start, end) = self.window(key);
+        let inside = (start == 0 || self.keys[start - 1] < key) && (end == self.keys.len() || compare(&self.keys[end], &key) == Ordering::Greater);
+        if inside {
+            let offset = |idx: usize| start + idx;
+            return simd::scan(&self.keys[start..end], key).map(offset).map_err(offset);
+        }
+        Strategy::Exponential { hint: prediction }.search_by(self.keys, |probe| compare(probe, &key))
+    }
+
+    /// Returns the predicted position of `key` and the window `start..end` that `epsilon` allows.
+    fn window(&self, key: T) -> (usize, usize, usize) {
+        let segment = match self.strategy.partition_point(&self.starts, |start| compare(start, &key) != Ordering::Greater) {
+            0 => return (0, 0, 0),
+            idx => &self.segments[idx - 1],
+        };
+        let prediction = segment.rank as f64 + segment.slope * (key.position() - segment.position);
+        let prediction = if prediction.is_nan() { segment.rank } else { prediction.clamp(0.0, self.keys.len() as f64) as usize };
+        // One extra position on each side absorbs rounding, and one more on the right lets a
+        // missing key's insertion point land just past the last key within the bound.
+        let start = prediction.saturating_sub(self.epsilon + 1);
+        let end = (prediction + self.epsilon + 2).min(self.keys.len());
+        (prediction, start, end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PgmIndex;
+
+    #[test]
+    fn test_error_bound_and_search() {
+        let curved: Vec<u64> = (0..20_000u64).map(|x| x * x / 7 + 3 * x).collect();
+        let steps: Vec<u64> = (0..20_000u64).map(|x| (x / 100) * 1_000_000 + x % 100).collect();
+        let duplicates: Vec<u64> = (0..20_000u64).map(|x| x / 50).collect();
+        for keys in [curved, steps, duplicates].iter() {
+            for &epsilon in [0, 4, 64].iter() {
+                let index = PgmIndex::new(keys, epsilon);
+                assert!(index.segments() >= 1);
+                for key in keys.iter().step_by(13) {
+                    let expected = keys.partition_point(|k| k < key);
+                    let (prediction, _, _) = index.window(*key);
+                    assert!(prediction.abs_diff(expected) <= epsilon + 1);
+                    assert_eq!(Some(expected), index.find(key));
+                    assert_eq!(keys.binary_search(&(key + 1)).is_ok(), index.search(&(key + 1)).is_ok());
+                    if let Err(idx) = keys.binary_search(&(key + 1)) {
+                        assert_eq!(Err(idx), index.search(&(key + 1)));
+                    }
+                }
+                assert_eq!(Err(keys.len()), index.search(&u64::MAX));
+            }
+        }
+        // Nanosecond timestamps are above 2^53, so neighbouring keys share a position.
+        let timestamps: Vec<u64> = (0..100_000).map(|x| 1_700_000_000_000_000_000 + x).collect();
+        for &epsilon in [4, 16, 64].iter() {
+            let index = PgmIndex::new(&timestamps, epsilon);
+            for (rank, key) in timestamps.iter().enumerate() {
+                assert_eq!(Some(rank), index.find(key));
+            }
+        }
+        let floats = [f64::NEG_INFINITY, -1.0, 0.0, 2.0, f64::INFINITY];
+        let index = PgmIndex::new(&floats, 0);
+        for (rank, key) in floats.iter().enumerate() {
+            assert_eq!(Some(rank), index.find(key));
+        }
+        let linear: Vec<u64> = (0..100_000).map(|x| x * 8).collect();
+        assert_eq!(1, PgmIndex::new(&linear, 8).segments());
+        assert_eq!(Some(3), PgmIndex::new(&[0.5, 1.0, 1.5, 2.0], 1).find(&2.0));
+        assert_eq!(Err(0), PgmIndex::<u32>::new(&[], 4).search(&7));
+        let small: Vec<u64> = (0..10).collect();
+        assert_eq!(Some(5), PgmIndex::new(&small, usize::MAX).find(&5));
+    }
+}
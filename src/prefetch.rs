@@ -0,0 +1,20 @@
+/// Hints the CPU to start loading the cache line that holds `ptr` into L1.
+///
+/// `ptr` does not need to point into a live allocation: prefetches never fault, so callers may
+/// prefetch speculatively past the end of a collection. This is a no-op on targets without a
+/// prefetch instruction.
+#[inline(always)]
+pub(crate) fn prefetch<T>(ptr: *const T) {
+    #[cfg(target_arch = "x86_64")]
+    // SAFETY: SSE is part of the x86_64 baseline, and prefetching has no architectural side effects.
+    unsafe {
+        core::arch::x86_64::_mm_prefetch(ptr as *const i8, core::arch::x86_64::_MM_HINT_T0);
+    }
+    #[cfg(all(target_arch = "x86", target_feature = "sse"))]
+    // SAFETY: SSE is enabled for this target, and prefetching has no architectural side effects.
+    unsafe {
+        core::arch::x86::_mm_prefetch(ptr as *const i8, core::arch::x86::_MM_HINT_T0);
+    }
+    #[cfg(not(any(target_arch = "x86_64", all(target_arch = "x86", target_feature = "sse"))))]
+    let _ = ptr;
+}
@@ -0,0 +1,106 @@
+use std::borrow::Borrow;
+use std::ops::{Bound, Range, RangeBounds};
+use std::slice::Iter;
+
+use crate::bounds::partition_point;
+use crate::HardwareProfile;
+
+/// The elements of a sorted collection that fall within a range, as returned by [`range`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeMatch<'a, T> {
+    /// The matching elements.
+    pub slice: &'a [T],
+    /// The indices of the matching elements in the original collection.
+    pub indices: Range<usize>,
+}
+
+impl<'a, T> RangeMatch<'a, T> {
+    /// Returns the number of matching elements.
+    pub fn len(&self) -> usize {
+        self.slice.len()
+    }
+
+    /// Returns `true` if no elements fall within the range.
+    pub fn is_empty(&self) -> bool {
+        self.slice.is_empty()
+    }
+
+    /// Iterates over the matching elements.
+    pub fn iter(&self) -> Iter<'a, T> {
+        self.slice.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for RangeMatch<'a, T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.slice.iter()
+    }
+}
+
+/// Returns the elements of a sorted collection that fall within `range`.
+///
+/// Like `BTreeMap::range`, the bounds may be any borrowed form of the element type. The end of the
+/// range is only searched for past its start, so the second search covers a narrower slice.
+pub fn range<T, Q, R>(collection: &[T], range: R) -> RangeMatch<'_, T>
+where T: Borrow<Q>, Q: ?Sized + Ord, R: RangeBounds<Q>
+{
+    range_with_profile(collection, range, &HardwareProfile::current_or_conservative())
+}
+
+/// Internal range query, tuned against an explicit hardware profile.
+fn range_with_profile<'a, T, Q, R>(collection: &'a [T], range: R, profile: &HardwareProfile) -> RangeMatch<'a, T>
+where T: Borrow<Q>, Q: ?Sized + Ord, R: RangeBounds<Q>
+{
+    let start = match range.start_bound() {
+        Bound::Included(a) => partition_point(collection, profile, |probe| probe.borrow() < a),
+        Bound::Excluded(a) => partition_point(collection, profile, |probe| probe.borrow() <= a),
+        Bound::Unbounded => 0,
+    };
+    let rest = &collection[start..];
+    let len = match range.end_bound() {
+        Bound::Included(b) => partition_point(rest, profile, |probe| probe.borrow() <= b),
+        Bound::Excluded(b) => partition_point(rest, profile, |probe| probe.borrow() < b),
+        Bound::Unbounded => rest.len(),
+    };
+    RangeMatch { slice: &rest[..len], indices: start..start + len }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Bound;
+
+    use super::{range, range_with_profile};
+    use crate::{CacheLevel, HardwareProfile};
+
+    #[test]
+    fn test_range() {
+        let vec: Vec<u32> = (0..500).map(|x| x / 2 * 3).collect();
+        for line_size in [64, 128, 4096].iter() {
+            let cache = CacheLevel { size: 1 << 20, line_size: *line_size, associativity: 8 };
+            let profile = HardwareProfile { l1: Some(cache), l2: None, l3: None };
+            for a in (0..760).step_by(7) {
+                for b in (0..760).step_by(11) {
+                    let expected: Vec<usize> = (0..vec.len()).filter(|&i| a <= vec[i] && vec[i] < b).collect();
+                    let found = range_with_profile(&vec, a..b, &profile);
+                    assert_eq!(expected.len(), found.len());
+                    if let Some(first) = expected.first() {
+                        assert_eq!(*first..*first + expected.len(), found.indices);
+                    }
+                    assert!(found.iter().all(|v| a <= *v && *v < b));
+                }
+            }
+        }
+
+        assert_eq!(2..6, range(&vec, 3..=6).indices);
+        assert_eq!(4..6, range(&vec, (Bound::Excluded(3), Bound::Included(6))).indices);
+        assert_eq!(494..500, range(&vec, 741..).indices);
+        assert_eq!(&[0, 0, 3, 3][..], range(&vec, ..4).slice);
+
+        let strings: Vec<String> = ["ant", "bee", "cat", "dog", "eel"].iter().map(|s| s.to_string()).collect();
+        let found: Vec<&str> = range::<_, str, _>(&strings, (Bound::Included("b"), Bound::Excluded("d"))).into_iter().map(|s| s.as_str()).collect();
+        assert_eq!(vec!["bee", "cat"], found);
+    }
+}
@@ -0,0 +1,249 @@
+use crate::strategy::check;
+
+/// Primitive keys whose sorted blocks can be scanned with SIMD comparisons.
+///
+/// On x86_64 the integer and float implementations compare 16 or 32 bytes at a time with SSE2 or
+/// AVX2, picked at runtime; everything else uses the scalar default.
+pub trait SimdKey: Copy + PartialOrd {
+    /// Returns how many elements of `block` are less than `key`, which for a sorted block is the
+    /// index of the first element not less than `key`.
+    fn count_less(block: &[Self], key: Self) -> usize {
+        block.iter().filter(|v| **v < key).count()
+    }
+}
+
+/// Scans a whole sorted collection for `key`, with `slice::binary_search` semantics.
+///
+/// Intended for small collections (a few cache lines), where comparing every element with SIMD
+/// beats the unpredictable branches of binary search.
+pub(crate) fn scan<T>(collection: &[T], key: T) -> Result<usize, usize>
+where T: SimdKey
+{
+    check(collection, T::count_less(collection, key), |probe| *probe == key)
+}
+
+/// Jump search whose final block is scanned with [`SimdKey::count_less`].
+pub(crate) fn search_jump<T>(collection: &[T], jump_size: usize, key: T) -> Result<usize, usize>
+where T: SimdKey
+{
+    let jump_size = jump_size.max(1);
+    let mut i = jump_size;
+    while i < collection.len() && collection[i] < key {
+        i += jump_size;
+    }
+    let left = i - jump_size;
+    let right = (i + 1).min(collection.len());
+    check(collection, left + T::count_less(&collection[left..right], key), |probe| *probe == key)
+}
+
+macro_rules! impl_simd_key {
+    ($($t:ty => $sse:ident, $avx2:ident, $feature:tt;)*) => {
+        $(
+            impl SimdKey for $t {
+                fn count_less(block: &[Self], key: Self) -> usize {
+                    #[cfg(target_arch = "x86_64")]
+                    {
+                        if block.len() >= 32 / std::mem::size_of::<$t>() && is_x86_feature_detected!("avx2") {
+                            // SAFETY: AVX2 support was just checked.
+                            return unsafe { x86::$avx2(block, key) };
+                        }
+                        if block.len() >= 16 / std::mem::size_of::<$t>() && is_x86_feature_detected!($feature) {
+                            // SAFETY: support for the 128-bit kernel's feature was just checked.
+                            return unsafe { x86::$sse(block, key) };
+                        }
+                    }
+                    block.iter().filter(|v| **v < key).count()
+                }
+            }
+        )*
+    };
+}
+
+impl_simd_key! {
+    i8 => i8_sse, i8_avx2, "sse2";
+    u8 => u8_sse, u8_avx2, "sse2";
+    i16 => i16_sse, i16_avx2, "sse2";
+    u16 => u16_sse, u16_avx2, "sse2";
+    i32 => i32_sse, i32_avx2, "sse2";
+    u32 => u32_sse, u32_avx2, "sse2";
+    i64 => i64_sse, i64_avx2, "sse4.2";
+    u64 => u64_sse, u64_avx2, "sse4.2";
+    f32 => f32_sse, f32_avx2, "sse2";
+    f64 => f64_sse, f64_avx2, "sse2";
+}
+
+/// Pointer-sized integers reuse the kernels of the fixed-width integer of the same size.
+macro_rules! impl_simd_key_as {
+    ($($t:ty => $width:literal as $fixed:ty,)*) => {
+        $(
+            #[cfg(target_pointer_width = $width)]
+            impl SimdKey for $t {
+                fn count_less(block: &[Self], key: Self) -> usize {
+                    // SAFETY: the two types have the same size, alignment and value range on this target.
+                    let block = unsafe { std::slice::from_raw_parts(block.as_ptr() as *const $fixed, block.len()) };
+                    <$fixed>::count_less(block, key as $fixed)
+                }
+            }
+        )*
+    };
+}
+
+impl_simd_key_as! {
+    usize => "64" as u64,
+    isize => "64" as i64,
+    usize => "32" as u32,
+    isize => "32" as i32,
+}
+
+#[cfg(not(any(target_pointer_width = "32", target_pointer_width = "64")))]
+impl SimdKey for usize {}
+#[cfg(not(any(target_pointer_width = "32", target_pointer_width = "64")))]
+impl SimdKey for isize {}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use std::arch::x86_64::*;
+    use std::mem::size_of;
+
+    /// Integer kernels. Unsigned keys are biased into signed range, since x86 only has signed
+    /// integer comparisons; `movemask_epi8` yields one bit per byte, hence the final division.
+    macro_rules! int_kernels {
+        ($($t:ty as $s:ty, $bias:expr => $sse:ident($feature:tt, $set1:ident, $cmpgt:ident), $avx2:ident($set1_256:ident, $cmpgt_256:ident);)*) => {
+            $(
+                #[target_feature(enable = $feature)]
+                pub(super) unsafe fn $sse(block: &[$t], key: $t) -> usize {
+                    let bias = $set1($bias);
+                    let key_vec = $set1((key as $s) ^ $bias);
+                    let chunks = block.chunks_exact(16 / size_of::<$t>());
+                    let rest = chunks.remainder();
+                    let mut bits = 0;
+                    for chunk in chunks {
+                        let v = _mm_xor_si128(_mm_loadu_si128(chunk.as_ptr() as *const __m128i), bias);
+                        bits += (_mm_movemask_epi8($cmpgt(key_vec, v)) as u32).count_ones() as usize;
+                    }
+                    bits / size_of::<$t>() + rest.iter().filter(|v| **v < key).count()
+                }
+
+                #[target_feature(enable = "avx2")]
+                pub(super) unsafe fn $avx2(block: &[$t], key: $t) -> usize {
+                    let bias = $set1_256($bias);
+                    let key_vec = $set1_256((key as $s) ^ $bias);
+                    let chunks = block.chunks_exact(32 / size_of::<$t>());
+                    let rest = chunks.remainder();
+                    let mut bits = 0;
+                    for chunk in chunks {
+                        let v = _mm256_xor_si256(_mm256_loadu_si256(chunk.as_ptr() as *const __m256i), bias);
+                        bits += (_mm256_movemask_epi8($cmpgt_256(key_vec, v)) as u32).count_ones() as usize;
+                    }
+                    bits / size_of::<$t>() + rest.iter().filter(|v| **v < key).count()
+                }
+            )*
+        };
+    }
+
+    int_kernels! {
+        i8 as i8, 0 => i8_sse("sse2", _mm_set1_epi8, _mm_cmpgt_epi8), i8_avx2(_mm256_set1_epi8, _mm256_cmpgt_epi8);
+        u8 as i8, i8::MIN => u8_sse("sse2", _mm_set1_epi8, _mm_cmpgt_epi8), u8_avx2(_mm256_set1_epi8, _mm256_cmpgt_epi8);
+        i16 as i16, 0 => i16_sse("sse2", _mm_set1_epi16, _mm_cmpgt_epi16), i16_avx2(_mm256_set1_epi16, _mm256_cmpgt_epi16);
+        u16 as i16, i16::MIN => u16_sse("sse2", _mm_set1_epi16, _mm_cmpgt_epi16), u16_avx2(_mm256_set1_epi16, _mm256_cmpgt_epi16);
+        i32 as i32, 0 => i32_sse("sse2", _mm_set1_epi32, _mm_cmpgt_epi32), i32_avx2(_mm256_set1_epi32, _mm256_cmpgt_epi32);
+        u32 as i32, i32::MIN => u32_sse("sse2", _mm_set1_epi32, _mm_cmpgt_epi32), u32_avx2(_mm256_set1_epi32, _mm256_cmpgt_epi32);
+        i64 as i64, 0 => i64_sse("sse4.2", _mm_set1_epi64x, _mm_cmpgt_epi64), i64_avx2(_mm256_set1_epi64x, _mm256_cmpgt_epi64);
+        u64 as i64, i64::MIN => u64_sse("sse4.2", _mm_set1_epi64x, _mm_cmpgt_epi64), u64_avx2(_mm256_set1_epi64x, _mm256_cmpgt_epi64);
+    }
+
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn f32_sse(block: &[f32], key: f32) -> usize {
+        let key_vec = _mm_set1_ps(key);
+        let chunks = block.chunks_exact(4);
+        let rest = chunks.remainder();
+        let mut count = 0;
+        for chunk in chunks {
+            count += (_mm_movemask_ps(_mm_cmplt_ps(_mm_loadu_ps(chunk.as_ptr()), key_vec)) as u32).count_ones() as usize;
+        }
+        count + rest.iter().filter(|v| **v < key).count()
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn f32_avx2(block: &[f32], key: f32) -> usize {
+        let key_vec = _mm256_set1_ps(key);
+        let chunks = block.chunks_exact(8);
+        let rest = chunks.remainder();
+        let mut count = 0;
+        for chunk in chunks {
+            let lt = _mm256_cmp_ps::<_CMP_LT_OQ>(_mm256_loadu_ps(chunk.as_ptr()), key_vec);
+            count += (_mm256_movemask_ps(lt) as u32).count_ones() as usize;
+        }
+        count + rest.iter().filter(|v| **v < key).count()
+    }
+
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn f64_sse(block: &[f64], key: f64) -> usize {
+        let key_vec = _mm_set1_pd(key);
+        let chunks = block.chunks_exact(2);
+        let rest = chunks.remainder();
+        let mut count = 0;
+        for chunk in chunks {
+            count += (_mm_movemask_pd(_mm_cmplt_pd(_mm_loadu_pd(chunk.as_ptr()), key_vec)) as u32).count_ones() as usize;
+        }
+        count + rest.iter().filter(|v| **v < key).count()
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn f64_avx2(block: &[f64], key: f64) -> usize {
+        let key_vec = _mm256_set1_pd(key);
+        let chunks = block.chunks_exact(4);
+        let rest = chunks.remainder();
+        let mut count = 0;
+        for chunk in chunks {
+            let lt = _mm256_cmp_pd::<_CMP_LT_OQ>(_mm256_loadu_pd(chunk.as_ptr()), key_vec);
+            count += (_mm256_movemask_pd(lt) as u32).count_ones() as usize;
+        }
+        count + rest.iter().filter(|v| **v < key).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{scan, search_jump, SimdKey};
+
+    fn check_type<T>(values: Vec<T>, probes: &[T])
+    where T: SimdKey + std::fmt::Debug
+    {
+        for len in 0..values.len() {
+            let block = &values[..len];
+            for probe in probes.iter() {
+                let expected = block.iter().filter(|v| **v < *probe).count();
+                assert_eq!(expected, T::count_less(block, *probe), "len {} probe {:?}", len, probe);
+            }
+        }
+    }
+
+    #[test]
+    fn test_count_less_matches_scalar() {
+        check_type((-40..40).map(|x| x as i8 * 3).collect(), &[i8::MIN, -7, 0, 5, 117, i8::MAX]);
+        check_type((0..80).map(|x| x as u8 * 3).collect(), &[0, 1, 128, 200, u8::MAX]);
+        check_type((-40..40).map(|x| x as i16 * 800).collect(), &[i16::MIN, -801, 0, 799, i16::MAX]);
+        check_type((0..80).map(|x| x as u16 * 800).collect(), &[0, 40_000, 63_200, u16::MAX]);
+        check_type((-40..40).map(|x| x * 50_000_000).collect::<Vec<i32>>(), &[i32::MIN, -1, 0, 1_999_999_999]);
+        check_type((0..80).map(|x| x * 50_000_000).collect::<Vec<u32>>(), &[0, 2_100_000_000, 3_950_000_000, u32::MAX]);
+        check_type((-40..40).map(|x| x << 56).collect::<Vec<i64>>(), &[i64::MIN, -1, 0, 1 << 60]);
+        check_type((0..80).map(|x| x << 57).collect::<Vec<u64>>(), &[0, 1 << 63, (1 << 63) + 1, u64::MAX]);
+        check_type((-40..40).map(|x| x as f32 * 0.5).collect(), &[f32::NEG_INFINITY, -3.25, 0.0, 19.5, f32::NAN]);
+        check_type((-40..40).map(|x| x as f64 * 0.5).collect(), &[f64::NEG_INFINITY, -3.25, 0.0, 19.5, f64::NAN]);
+        check_type((0..80).collect::<Vec<usize>>(), &[0, 17, 100]);
+        check_type((0..80).map(|x| x << 40).collect::<Vec<usize>>(), &[0, 17 << 40, usize::MAX]);
+        check_type((-40..40).collect::<Vec<isize>>(), &[isize::MIN, -7, 0, 39, isize::MAX]);
+    }
+
+    #[test]
+    fn test_scan_and_jump() {
+        let vec: Vec<u32> = (0..1000).map(|x| x / 3 * 2).collect();
+        for x in 0..700 {
+            let lower = vec.partition_point(|v| *v < x);
+            let expected = if vec.get(lower) == Some(&x) { Ok(lower) } else { Err(lower) };
+            assert_eq!(expected, scan(&vec, x));
+            assert_eq!(expected, search_jump(&vec, 31, x));
+        }
+    }
+}
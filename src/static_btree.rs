@@ -0,0 +1,168 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::mem::size_of;
+
+use crate::aligned::AlignedSlots;
+use crate::strategy::DEFAULT_LINE_SIZE;
+use crate::HardwareProfile;
+
+/// A read-only implicit B-tree (S-tree) index whose nodes are exactly one cache line wide.
+///
+/// Each node holds `B = line_size / size_of::<T>()` keys, so visiting a node costs one cache line
+/// and the tree is only `log_{B+1}(n)` levels deep, compared to `log_2(n)` for binary search. The
+/// keys within a node are compared without branches by counting how many are less than the target,
+/// which the compiler vectorises for primitive keys.
+///
+/// Lookups return indices into the original sorted collection.
+#[derive(Debug, Clone)]
+pub struct StaticBTreeIndex<T> {
+    /// Nodes of `width` keys each, padded at the end with copies of the largest key.
+    keys: AlignedSlots<T>,
+    /// The sorted index of each key, with `len` for padding.
+    ranks: Vec<usize>,
+    /// Number of keys per node.
+    width: usize,
+    len: usize,
+}
+
+impl<T> StaticBTreeIndex<T>
+where T: Clone
+{
+    /// Builds an index from a sorted collection, sizing nodes to the detected cache line.
+    pub fn new(sorted: &[T]) -> Self {
+        let line_size = HardwareProfile::current_or_conservative().line_size().unwrap_or(DEFAULT_LINE_SIZE);
+        StaticBTreeIndex::with_node_width(sorted, line_size / size_of::<T>().max(1))
+    }
+
+    /// Builds an index from a sorted collection with `width` keys per node.
+    ///
+    /// Nodes wider than the collection would only hold padding, so `width` is clamped to its length.
+    pub fn with_node_width(sorted: &[T], width: usize) -> Self {
+        let width = width.clamp(1, sorted.len().max(1));
+        let last = match sorted.last() {
+            Some(last) => last,
+            None => return StaticBTreeIndex { keys: AlignedSlots::empty(), ranks: Vec::new(), width, len: 0 },
+        };
+        let nodes = sorted.len().div_ceil(width);
+        let line_size = width * size_of::<T>().max(1);
+        let mut index = StaticBTreeIndex {
+            keys: AlignedSlots::new(nodes * width, last, line_size),
+            ranks: vec![sorted.len(); nodes * width],
+            width,
+            len: sorted.len(),
+        };
+        let mut next = 0;
+        index.fill(sorted, &mut next, 0);
+        index
+    }
+
+    /// Places `sorted[next..]` into the subtree rooted at node `k` by in-order traversal.
+    fn fill(&mut self, sorted: &[T], next: &mut usize, k: usize) {
+        if k * self.width < self.ranks.len() {
+            for i in 0..self.width {
+                self.fill(sorted, next, self.child(k, i));
+                if *next < sorted.len() {
+                    self.keys[k * self.width + i] = sorted[*next].clone();
+                    self.ranks[k * self.width + i] = *next;
+                    *next += 1;
+                }
+            }
+            self.fill(sorted, next, self.child(k, self.width));
+        }
+    }
+}
+
+impl<T> StaticBTreeIndex<T> {
+    /// Returns the number of indexed elements.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the index holds no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of keys per node.
+    pub fn node_width(&self) -> usize {
+        self.width
+    }
+
+    /// Looks up an element, returning its index in the original sorted collection.
+    pub fn find<Q>(&self, item: &Q) -> Option<usize>
+    where T: Borrow<Q>, Q: ?Sized + Ord
+    {
+        self.search(item).ok()
+    }
+
+    /// Like [`StaticBTreeIndex::find`], but returns the insertion point on a miss, as in `slice::binary_search`.
+    pub fn search<Q>(&self, item: &Q) -> Result<usize, usize>
+    where T: Borrow<Q>, Q: ?Sized + Ord
+    {
+        self.search_by(|probe| probe.borrow().cmp(item))
+    }
+
+    /// Like [`StaticBTreeIndex::search`], but with a comparator that orders a probed element relative to the target.
+    pub fn search_by<F>(&self, mut f: F) -> Result<usize, usize>
+    where F: FnMut(&T) -> Ordering
+    {
+        match self.lower_bound_slot(&mut f) {
+            Some(slot) if f(&self.keys[slot]) == Ordering::Equal => Ok(self.ranks[slot]),
+            Some(slot) => Err(self.ranks[slot]),
+            None => Err(self.len),
+        }
+    }
+
+    /// Returns the index in the original sorted collection of the first element not less than `item`.
+    pub fn lower_bound<Q>(&self, item: &Q) -> usize
+    where T: Borrow<Q>, Q: ?Sized + Ord
+    {
+        self.lower_bound_slot(|probe| probe.borrow().cmp(item)).map_or(self.len, |slot| self.ranks[slot])
+    }
+
+    /// Returns the slot of the first key not less than the target, if any.
+    fn lower_bound_slot<F>(&self, mut f: F) -> Option<usize>
+    where F: FnMut(&T) -> Ordering
+    {
+        let keys = self.keys.as_slice();
+        let mut result = None;
+        let mut k = 0;
+        while k * self.width < self.ranks.len() {
+            let node = &keys[k * self.width..(k + 1) * self.width];
+            let i: usize = node.iter().map(|key| (f(key) == Ordering::Less) as usize).sum();
+            if i < self.width {
+                result = Some(k * self.width + i);
+            }
+            k = self.child(k, i);
+        }
+        result.filter(|slot| self.ranks[*slot] < self.len)
+    }
+
+    /// Returns the index of the `i`-th child of node `k`.
+    fn child(&self, k: usize, i: usize) -> usize {
+        k * (self.width + 1) + i + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StaticBTreeIndex;
+    use crate::testing::{assert_lower_bound_matches, assert_search_matches, sorted_fixtures};
+
+    #[test]
+    fn test_matches_sorted_search() {
+        for width in [1, 2, 3, 16].iter() {
+            for vec in sorted_fixtures() {
+                let index = StaticBTreeIndex::with_node_width(&vec, *width);
+                assert_eq!(vec.len(), index.len());
+                assert_lower_bound_matches(&vec, |x| index.lower_bound(&x));
+                assert_search_matches(&vec, |x| index.search(&x));
+            }
+        }
+        let vec: Vec<u64> = (0..5000).collect();
+        assert_eq!(Some(4321), StaticBTreeIndex::new(&vec).find(&4321));
+        let wide = StaticBTreeIndex::with_node_width(&vec, 1 << 62);
+        assert_eq!(vec.len(), wide.node_width());
+        assert_eq!(Some(4321), wide.find(&4321));
+    }
+}
@@ -0,0 +1,216 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::mem::size_of;
+
+use crate::interpolation::{self, Interpolate};
+use crate::simd::{self, SimdKey};
+use crate::{branchless, exponential, fibonacci, jump, HardwareProfile};
+
+/// Line size assumed when the profile does not report one.
+pub(crate) const DEFAULT_LINE_SIZE: usize = 64;
+
+/// The search algorithms that the cache-aware dispatcher can choose between.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// Scan the whole collection; used when it spans a single cache line.
+    Linear,
+    /// Jump search with `sqrt(n)` blocks.
+    Jump,
+    /// Binary search.
+    Binary,
+    /// Exponential (galloping) search outwards from `hint`; never chosen by [`Strategy::select`].
+    Exponential { hint: usize },
+    /// Branchless binary search, optionally prefetching both possible next probes; never chosen by [`Strategy::select`].
+    Branchless { prefetch: bool },
+    /// Fibonacci search, which splits ranges at Fibonacci numbers instead of halving them; never chosen by [`Strategy::select`].
+    Fibonacci,
+    /// Interpolation search. It needs numeric keys, so comparator-based searches fall back to binary search.
+    Interpolation,
+}
+
+/// What the caller knows about where targets tend to be, used by the `*_with_hint` searches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+    /// Nothing is known; the strategy is chosen from the hardware profile alone.
+    None,
+    /// Targets tend to be near the beginning of the collection.
+    Front,
+    /// Targets tend to be near the given index, e.g. the previous hit.
+    Near(usize),
+}
+
+impl Strategy {
+    /// Picks a strategy for searching `len` elements of type `T` on the given hardware.
+    ///
+    /// The choice is made in bytes rather than element counts, so a `u8` and a 512-byte struct
+    /// of the same length can get different strategies:
+    ///
+    /// - if the whole collection fits in one cache line, a linear scan touches nothing else;
+    /// - if a jump search block fits in one cache line and the collection fits in L2, the strided
+    ///   fence reads are cheap and the final scan stays within a single line;
+    /// - otherwise binary search touches the fewest lines.
+    pub fn select<T>(profile: &HardwareProfile, len: usize) -> Strategy {
+        let element_size = size_of::<T>().max(1);
+        let line_size = profile.line_size().unwrap_or(DEFAULT_LINE_SIZE);
+        let elements_per_line = (line_size / element_size).max(1);
+        if len <= elements_per_line {
+            return Strategy::Linear;
+        }
+        let footprint = len.saturating_mul(element_size);
+        let block_size = jump_size(len).saturating_mul(element_size);
+        if block_size <= line_size && residency(profile, footprint) <= 2 {
+            Strategy::Jump
+        } else {
+            Strategy::Binary
+        }
+    }
+
+    /// Like [`Strategy::select`], but gallops from the hinted position when the caller gives one.
+    pub fn select_with_hint<T>(profile: &HardwareProfile, len: usize, hint: Hint) -> Strategy {
+        match hint {
+            Hint::None => Strategy::select::<T>(profile, len),
+            Hint::Front => Strategy::Exponential { hint: 0 },
+            Hint::Near(idx) => Strategy::Exponential { hint: idx },
+        }
+    }
+
+    /// Like [`Strategy::select`], but for numeric keys, where interpolation search is also considered.
+    ///
+    /// Interpolation is chosen when a linear scan is not enough and a sample of the collection
+    /// suggests its keys are roughly uniformly distributed.
+    pub fn select_numeric<T, Q>(profile: &HardwareProfile, collection: &[T]) -> Strategy
+    where T: Borrow<Q>, Q: ?Sized + Interpolate
+    {
+        match Strategy::select::<T>(profile, collection.len()) {
+            Strategy::Linear => Strategy::Linear,
+            _ if interpolation::looks_uniform(collection) => Strategy::Interpolation,
+            strategy => strategy,
+        }
+    }
+
+    /// Like [`Strategy::search_by`], but searches for a numeric key, so interpolation search can be used.
+    pub fn search_numeric<T, Q>(self, collection: &[T], item: &Q) -> Result<usize, usize>
+    where T: Borrow<Q>, Q: ?Sized + Interpolate + PartialOrd
+    {
+        match self {
+            Strategy::Interpolation => interpolation::search(collection, item),
+            strategy => strategy.search_by(collection, |probe| interpolation::compare(probe.borrow(), item)),
+        }
+    }
+
+    /// Like [`Strategy::search_by`], but for primitive keys, so linear scans (all of [`Strategy::Linear`]
+    /// and the final block of [`Strategy::Jump`]) compare many elements at once with SIMD.
+    pub fn search_simd<T>(self, collection: &[T], key: &T) -> Result<usize, usize>
+    where T: SimdKey
+    {
+        match self {
+            Strategy::Linear => simd::scan(collection, *key),
+            Strategy::Jump => simd::search_jump(collection, jump_size(collection.len()), *key),
+            strategy => strategy.search_by(collection, |probe| interpolation::compare(probe, key)),
+        }
+    }
+
+    /// Searches a sorted collection with this strategy, with `slice::binary_search_by` semantics.
+    pub fn search_by<T, F>(self, collection: &[T], f: F) -> Result<usize, usize>
+    where F: FnMut(&T) -> Ordering
+    {
+        match self {
+            Strategy::Linear => jump::linear_search_by(collection, 0, collection.len(), f),
+            Strategy::Jump => jump::search_by(collection, jump_size(collection.len()), f),
+            Strategy::Binary | Strategy::Interpolation => collection.binary_search_by(f),
+            Strategy::Exponential { hint } => exponential::search_by(collection, hint, f),
+            Strategy::Branchless { prefetch } => branchless::search_by(collection, prefetch, f),
+            Strategy::Fibonacci => fibonacci::search_by(collection, f),
+        }
+    }
+
+    /// Returns the index of the first element that does not satisfy `pred`, with `slice::partition_point` semantics.
+    pub fn partition_point<T, P>(self, collection: &[T], mut pred: P) -> usize
+    where P: FnMut(&T) -> bool
+    {
+        match self {
+            Strategy::Linear => collection.iter().position(|v| !pred(v)).unwrap_or(collection.len()),
+            Strategy::Jump => jump::partition_point(collection, jump_size(collection.len()), pred),
+            Strategy::Binary | Strategy::Interpolation => collection.partition_point(pred),
+            Strategy::Exponential { hint } => exponential::partition_point(collection, hint, pred),
+            Strategy::Branchless { prefetch } => branchless::partition_point(collection, prefetch, pred),
+            Strategy::Fibonacci => fibonacci::partition_point(collection, pred),
+        }
+    }
+}
+
+/// Turns the lower bound `idx` of a search into `slice::binary_search` semantics: `Ok(idx)` if the
+/// element there `matches` the target, otherwise `Err(idx)` as the insertion point.
+pub(crate) fn check<T, P>(collection: &[T], idx: usize, matches: P) -> Result<usize, usize>
+where P: FnOnce(&T) -> bool
+{
+    match collection.get(idx) {
+        Some(probe) if matches(probe) => Ok(idx),
+        _ => Err(idx),
+    }
+}
+
+/// Returns the square root of the collection size, which is mathematically proven to be the optimal jump size for jump search.
+pub(crate) fn jump_size(len: usize) -> usize {
+    ((len as f64).sqrt()) as usize
+}
+
+/// Picks how many levels of jumps a multi-level jump search over `len` elements of type `T` should make.
+///
+/// Collections resident in L1 get classic single-level jump search, and each cache level further out
+/// adds a level, so the strided fence reads of larger collections stay within fewer lines. Levels
+/// that would leave a final block smaller than one cache line are dropped, as they only add probes.
+pub(crate) fn jump_levels<T>(profile: &HardwareProfile, len: usize) -> usize {
+    let element_size = size_of::<T>().max(1);
+    let line_size = profile.line_size().unwrap_or(DEFAULT_LINE_SIZE);
+    let elements_per_line = (line_size / element_size).max(1) as f64;
+    let mut levels = residency(profile, len.saturating_mul(element_size)) as usize;
+    while levels > 1 && (len as f64).powf(1.0 / levels as f64) <= elements_per_line {
+        levels -= 1;
+    }
+    levels
+}
+
+/// Returns the innermost cache level (1-3) that can hold `footprint` bytes, or 4 for main memory.
+pub(crate) fn residency(profile: &HardwareProfile, footprint: usize) -> u8 {
+    [(1, profile.l1), (2, profile.l2), (3, profile.l3)]
+        .iter()
+        .find(|(_, cache)| cache.is_some_and(|c| footprint <= c.size))
+        .map_or(4, |(level, _)| *level)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{jump_levels, Strategy};
+    use crate::testing::{assert_search_matches, sorted_fixtures};
+    use crate::HardwareProfile;
+
+    #[test]
+    fn test_select_accounts_for_element_size() {
+        let profile = HardwareProfile::conservative();
+        assert_eq!(Strategy::Linear, Strategy::select::<u8>(&profile, 64));
+        assert_eq!(Strategy::Jump, Strategy::select::<u64>(&profile, 64));
+        assert_eq!(Strategy::Binary, Strategy::select::<u64>(&profile, 1000));
+        assert_eq!(Strategy::Jump, Strategy::select::<u8>(&profile, 1000));
+        assert_eq!(Strategy::Binary, Strategy::select::<[u8; 512]>(&profile, 1000));
+        assert_eq!(Strategy::Binary, Strategy::select::<u8>(&profile, 1 << 20));
+    }
+
+    #[test]
+    fn test_search_simd_matches_binary_search() {
+        for vec in sorted_fixtures() {
+            for strategy in [Strategy::Linear, Strategy::Jump, Strategy::Binary, Strategy::Fibonacci].iter() {
+                assert_search_matches(&vec, |x| strategy.search_simd(&vec, &x));
+            }
+        }
+    }
+
+    #[test]
+    fn test_jump_levels_grow_with_residency() {
+        let profile = HardwareProfile::conservative();
+        assert_eq!(1, jump_levels::<u64>(&profile, 1 << 10));
+        assert_eq!(2, jump_levels::<u64>(&profile, 1 << 14));
+        assert_eq!(4, jump_levels::<u64>(&profile, 1 << 24));
+        assert_eq!(1, jump_levels::<u8>(&profile, 1 << 12));
+    }
+}
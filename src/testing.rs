@@ -0,0 +1,25 @@
+/// Sorted `u32` collections of awkward lengths, with runs of duplicates and gaps between values.
+pub(crate) fn sorted_fixtures() -> impl Iterator<Item = Vec<u32>> {
+    [0, 1, 2, 3, 7, 8, 15, 16, 17, 100, 289, 1000, 1023, 1024, 1025].iter().map(|len| (0..*len).map(|x| x / 3 * 2).collect())
+}
+
+/// Asserts that `search` agrees with `slice::binary_search` on `sorted` for every key in and around its range.
+pub(crate) fn assert_search_matches<S>(sorted: &[u32], mut search: S)
+where S: FnMut(u32) -> Result<usize, usize>
+{
+    for x in 0..sorted.last().map_or(2, |last| last + 2) {
+        match search(x) {
+            Ok(idx) => assert_eq!(x, sorted[idx], "len {} key {}", sorted.len(), x),
+            Err(idx) => assert_eq!(sorted.binary_search(&x), Err(idx), "len {} key {}", sorted.len(), x),
+        }
+    }
+}
+
+/// Asserts that `lower_bound` agrees with `slice::partition_point` on `sorted` for every key in and around its range.
+pub(crate) fn assert_lower_bound_matches<L>(sorted: &[u32], mut lower_bound: L)
+where L: FnMut(u32) -> usize
+{
+    for x in 0..sorted.last().map_or(2, |last| last + 2) {
+        assert_eq!(sorted.partition_point(|v| *v < x), lower_bound(x), "len {} key {}", sorted.len(), x);
+    }
+}
@@ -0,0 +1,184 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+
+/// A read-only, cache-oblivious search index that stores a sorted collection in van Emde Boas order.
+///
+/// The implicit search tree of height `h` is split into a top tree of height `h / 2` and the bottom
+/// trees hanging off it, each laid out contiguously and recursively in the same way. Whatever the
+/// size of a cache line or page, some level of this recursion produces subtrees that fit one, so a
+/// search touches `O(log_B n)` blocks at every level of the memory hierarchy at once. Unlike
+/// [`EytzingerIndex`](crate::EytzingerIndex) and [`StaticBTreeIndex`](crate::StaticBTreeIndex), this
+/// does not depend on detecting any cache parameters.
+///
+/// Only the keys are stored, in a complete tree padded with copies of the last key, so as many fit
+/// in a line as in the sorted collection itself. Child positions are computed from per-depth
+/// tables (Brodal, Fagerberg and Jacob) and ranks from the BFS number of the node a search ends at.
+///
+/// Lookups return indices into the original sorted collection.
+#[derive(Debug, Clone)]
+pub struct VanEmdeBoasIndex<T> {
+    /// Keys in van Emde Boas order; the root is at position 0.
+    keys: Vec<T>,
+    /// Navigation tables, one per depth of the tree.
+    levels: Vec<Level>,
+    len: usize,
+}
+
+/// Where the subtrees whose roots lie at one depth sit in the layout.
+///
+/// Every depth below the root is the root depth of the bottom trees of exactly one recursive split.
+#[derive(Debug, Clone, Copy, Default)]
+struct Level {
+    /// Number of nodes in the top tree of that split.
+    top_size: usize,
+    /// Number of nodes in each bottom tree of that split.
+    bottom_size: usize,
+    /// Depth of the root of the top tree of that split.
+    top_depth: usize,
+}
+
+impl<T> VanEmdeBoasIndex<T>
+where T: Clone
+{
+    /// Builds an index from a sorted collection.
+    pub fn new(sorted: &[T]) -> Self {
+        let len = sorted.len();
+        let last = match sorted.last() {
+            Some(last) => last,
+            None => return VanEmdeBoasIndex { keys: Vec::new(), levels: Vec::new(), len },
+        };
+
+        // Number the nodes of a complete binary tree in BFS order (root 1, children 2k and 2k + 1)
+        // and emit them in van Emde Boas order, padding in-order positions past the end.
+        let height = (usize::BITS - len.leading_zeros()) as usize;
+        let mut order = Vec::with_capacity((1 << height) - 1);
+        layout(1, height, &mut order);
+        let keys = order.iter().map(|&bfs| sorted.get(in_order(bfs, height)).unwrap_or(last).clone()).collect();
+        let mut levels = vec![Level::default(); height];
+        split(0, height, &mut levels);
+        VanEmdeBoasIndex { keys, levels, len }
+    }
+}
+
+impl<T> VanEmdeBoasIndex<T> {
+    /// Returns the number of indexed elements.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the index holds no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Looks up an element, returning its index in the original sorted collection.
+    pub fn find<Q>(&self, item: &Q) -> Option<usize>
+    where T: Borrow<Q>, Q: ?Sized + Ord
+    {
+        self.search(item).ok()
+    }
+
+    /// Like [`VanEmdeBoasIndex::find`], but returns the insertion point on a miss, as in `slice::binary_search`.
+    pub fn search<Q>(&self, item: &Q) -> Result<usize, usize>
+    where T: Borrow<Q>, Q: ?Sized + Ord
+    {
+        self.search_by(|probe| probe.borrow().cmp(item))
+    }
+
+    /// Like [`VanEmdeBoasIndex::search`], but with a comparator that orders a probed element relative to the target.
+    pub fn search_by<F>(&self, mut f: F) -> Result<usize, usize>
+    where F: FnMut(&T) -> Ordering
+    {
+        match self.lower_bound_position(&mut f) {
+            Some((pos, rank)) if f(&self.keys[pos]) == Ordering::Equal => Ok(rank),
+            Some((_, rank)) => Err(rank),
+            None => Err(self.len),
+        }
+    }
+
+    /// Returns the index in the original sorted collection of the first element not less than `item`.
+    pub fn lower_bound<Q>(&self, item: &Q) -> usize
+    where T: Borrow<Q>, Q: ?Sized + Ord
+    {
+        self.lower_bound_position(|probe| probe.borrow().cmp(item)).map_or(self.len, |(_, rank)| rank)
+    }
+
+    /// Walks down from the root, returning the layout position and rank of the last node that was
+    /// not less than the target.
+    fn lower_bound_position<F>(&self, mut f: F) -> Option<(usize, usize)>
+    where F: FnMut(&T) -> Ordering
+    {
+        let height = self.levels.len();
+        let mut positions = [0; usize::BITS as usize];
+        let mut bfs = 1;
+        let mut result = None;
+        for depth in 0..height {
+            if depth > 0 {
+                let level = self.levels[depth];
+                positions[depth] = positions[level.top_depth] + level.top_size + (bfs & level.top_size) * level.bottom_size;
+            }
+            let pos = positions[depth];
+            let less = f(&self.keys[pos]) == Ordering::Less;
+            if !less {
+                result = Some((pos, bfs));
+            }
+            bfs = 2 * bfs + less as usize;
+        }
+        // Padding copies the last key, so the lower bound is never a padding node.
+        result.map(|(pos, bfs)| (pos, in_order(bfs, height)))
+    }
+}
+
+/// Returns the in-order index of BFS-numbered node `bfs` in a complete tree of the given height.
+fn in_order(bfs: usize, height: usize) -> usize {
+    let depth = (usize::BITS - 1 - bfs.leading_zeros()) as usize;
+    ((2 * (bfs - (1 << depth)) + 1) << (height - depth - 1)) - 1
+}
+
+/// Fills in the navigation tables for a subtree of the given height whose root lies at `depth`,
+/// splitting it the same way as [`layout`].
+fn split(depth: usize, height: usize, levels: &mut [Level]) {
+    if height <= 1 {
+        return;
+    }
+    let top = height / 2;
+    levels[depth + top] = Level { top_size: (1 << top) - 1, bottom_size: (1 << (height - top)) - 1, top_depth: depth };
+    split(depth, top, levels);
+    split(depth + top, height - top, levels);
+}
+
+/// Appends the BFS numbers of the complete subtree of the given height rooted at `root`, in van Emde Boas order.
+fn layout(root: usize, height: usize, order: &mut Vec<usize>) {
+    if height == 1 {
+        order.push(root);
+        return;
+    }
+    let top = height / 2;
+    layout(root, top, order);
+    for bottom_root in (root << top)..((root + 1) << top) {
+        layout(bottom_root, height - top, order);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{layout, VanEmdeBoasIndex};
+    use crate::testing::{assert_lower_bound_matches, assert_search_matches, sorted_fixtures};
+
+    #[test]
+    fn test_layout_order() {
+        let mut order = Vec::new();
+        layout(1, 4, &mut order);
+        assert_eq!(vec![1, 2, 3, 4, 8, 9, 5, 10, 11, 6, 12, 13, 7, 14, 15], order);
+    }
+
+    #[test]
+    fn test_matches_sorted_search() {
+        for vec in sorted_fixtures() {
+            let index = VanEmdeBoasIndex::new(&vec);
+            assert_eq!(vec.len(), index.len());
+            assert_lower_bound_matches(&vec, |x| index.lower_bound(&x));
+            assert_search_matches(&vec, |x| index.search(&x));
+        }
+    }
+}